@@ -0,0 +1,93 @@
+use crate::parser::{Node, NodeKind};
+use crate::path::Segment;
+use std::collections::HashMap;
+
+/// Which occurrence of a key that appears more than once in an object the
+/// rules apply to. JSON parsers disagree on which one wins, so disclosing
+/// more than one can leave the verifier unsure of the value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DuplicateKeys {
+    /// Apply the rules to every occurrence
+    #[default]
+    RevealAll,
+    /// Apply the rules to the first occurrence and redact the others
+    First,
+    /// Apply the rules to the last occurrence and redact the others, as most
+    /// parsers keep the last one
+    Last,
+    /// Treat duplicate keys as an error
+    Error,
+}
+
+/// A key that appears more than once in the same object.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DuplicateKey {
+    /// The path from the root to the object, made of keys and indices
+    pub object: Vec<Segment>,
+    /// The key, after decoding escapes
+    pub name: String,
+    /// The `[start, end)` offsets of each occurrence of the key, quotes
+    /// included, in document order
+    pub keys: Vec<[usize; 2]>,
+    /// The index into `keys` of the occurrence the rules apply to, or `None`
+    /// when they apply to all of them
+    pub kept: Option<usize>,
+}
+
+// Find the keys appearing more than once in the objects within the root
+// value `node`, sorted by their first occurrence
+pub(crate) fn find_duplicates(node: &Node, mode: DuplicateKeys) -> Vec<DuplicateKey> {
+    let mut duplicates = Vec::new();
+    collect_duplicates(node, mode, &mut Vec::new(), &mut duplicates);
+    duplicates.sort_by_key(|duplicate| duplicate.keys[0][0]);
+    duplicates
+}
+
+fn collect_duplicates(
+    node: &Node,
+    mode: DuplicateKeys,
+    path: &mut Vec<Segment>,
+    duplicates: &mut Vec<DuplicateKey>,
+) {
+    match &node.kind {
+        NodeKind::Object(members) => {
+            // The occurrences of each key, grouped in one pass
+            let mut occurrences: HashMap<&str, Vec<usize>> = HashMap::new();
+            for (i, member) in members.iter().enumerate() {
+                occurrences.entry(&member.name).or_default().push(i);
+            }
+            for (name, indices) in occurrences {
+                let keys: Vec<[usize; 2]> = indices
+                    .iter()
+                    .map(|&i| [members[i].key.start, members[i].key.end])
+                    .collect();
+                if keys.len() > 1 {
+                    let kept = match mode {
+                        DuplicateKeys::First => Some(0),
+                        DuplicateKeys::Last => Some(keys.len() - 1),
+                        DuplicateKeys::RevealAll | DuplicateKeys::Error => None,
+                    };
+                    duplicates.push(DuplicateKey {
+                        object: path.clone(),
+                        name: name.to_string(),
+                        keys,
+                        kept,
+                    });
+                }
+            }
+            for member in members {
+                path.push(Segment::Key(member.name.clone()));
+                collect_duplicates(&member.value, mode, path, duplicates);
+                path.pop();
+            }
+        }
+        NodeKind::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                path.push(Segment::Index(index as i64));
+                collect_duplicates(item, mode, path, duplicates);
+                path.pop();
+            }
+        }
+        NodeKind::String | NodeKind::Number | NodeKind::Literal => {}
+    }
+}
@@ -0,0 +1,115 @@
+use crate::parser::{Node, NodeKind};
+use crate::path::{resolve_index, Segment};
+use crate::scanner::decode_string;
+use std::cmp::Ordering;
+
+/// A predicate on an array element, written `[?(@.currency == 'USD')]` in a
+/// selector.
+///
+/// `path` leads from the element to the value being tested, using only
+/// [`Segment::Key`] and [`Segment::Index`]. Without a `condition` the filter
+/// only tests that the value exists.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Filter {
+    pub path: Vec<Segment>,
+    pub condition: Option<(Comparison, Literal)>,
+}
+
+/// A comparison operator in a [`Filter`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// A JSON scalar a [`Filter`] compares against.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Literal {
+    String(String),
+    Number(f64),
+    Bool(bool),
+    Null,
+}
+
+impl Filter {
+    // Whether the element `node` of the document `json` passes the filter,
+    // ignoring the members whose keys start at an offset in `shadowed`
+    pub(crate) fn matches(&self, node: &Node, json: &str, shadowed: &[usize]) -> bool {
+        let Some(value) = resolve(node, &self.path, shadowed) else {
+            return false;
+        };
+        let Some((comparison, literal)) = &self.condition else {
+            return true;
+        };
+        let ordering = Literal::from_node(value, json).and_then(|value| value.compare(literal));
+        match comparison {
+            Comparison::Eq => ordering == Some(Ordering::Equal),
+            Comparison::Ne => ordering != Some(Ordering::Equal),
+            Comparison::Lt => ordering == Some(Ordering::Less),
+            Comparison::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+            Comparison::Gt => ordering == Some(Ordering::Greater),
+            Comparison::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+        }
+    }
+}
+
+impl Literal {
+    // The scalar value of `node`, or `None` for containers and values that
+    // are not valid JSON scalars
+    fn from_node(node: &Node, json: &str) -> Option<Literal> {
+        let raw = &json[node.start..node.end];
+        match node.kind {
+            NodeKind::String => {
+                let content = raw.strip_prefix('"')?.strip_suffix('"')?;
+                Some(Literal::String(decode_string(content)))
+            }
+            NodeKind::Number => raw.parse().ok().map(Literal::Number),
+            NodeKind::Literal => match raw {
+                "true" => Some(Literal::Bool(true)),
+                "false" => Some(Literal::Bool(false)),
+                "null" => Some(Literal::Null),
+                _ => None,
+            },
+            NodeKind::Object(_) | NodeKind::Array(_) => None,
+        }
+    }
+
+    // Numbers and strings are ordered, other values only compare equal to the
+    // same value, and values of different types are incomparable
+    fn compare(&self, other: &Literal) -> Option<Ordering> {
+        match (self, other) {
+            (Literal::String(a), Literal::String(b)) => Some(a.cmp(b)),
+            (Literal::Number(a), Literal::Number(b)) => a.partial_cmp(b),
+            (Literal::Bool(a), Literal::Bool(b)) if a == b => Some(Ordering::Equal),
+            (Literal::Null, Literal::Null) => Some(Ordering::Equal),
+            _ => None,
+        }
+    }
+}
+
+// Follow a path of keys and indices down from `node`, skipping shadowed
+// members. When an object still has the same key more than once the last one
+// wins, as with most JSON parsers.
+fn resolve<'a>(node: &'a Node, path: &[Segment], shadowed: &[usize]) -> Option<&'a Node> {
+    let Some((segment, rest)) = path.split_first() else {
+        return Some(node);
+    };
+    let child = match (segment, &node.kind) {
+        (Segment::Key(key), NodeKind::Object(members)) => members
+            .iter()
+            .rev()
+            .find(|member| {
+                &member.name == key && shadowed.binary_search(&member.key.start).is_err()
+            })
+            .map(|member| &member.value),
+        (Segment::Index(index), NodeKind::Array(items)) => {
+            resolve_index(*index, items.len()).map(|index| &items[index])
+        }
+        _ => None,
+    }?;
+    resolve(child, rest, shadowed)
+}
@@ -0,0 +1,683 @@
+use crate::duplicates::DuplicateKeys;
+use crate::offsets::OffsetUnit;
+use crate::parser::ParseError;
+use crate::policy::{Disclosure, Policy};
+use crate::ranges::{find_policy_ranges, merge_ranges, try_find_policy_ranges, Options};
+use std::fmt;
+
+/// Which parts of an HTTP/1.1 transcript to disclose.
+///
+/// The start line, header names and the framing between them are always kept,
+/// like the keys and structural characters of a JSON document. Header values
+/// are disclosed according to `headers`, which redacts them all by default
+/// and keeps `Set-Cookie`, `WWW-Authenticate` and `Proxy-Authenticate` hidden
+/// even when others are revealed by default, unless a rule names them, and
+/// the body according to `body`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpPolicy {
+    /// How header values are disclosed
+    pub headers: HeaderPolicy,
+    /// How the JSON body is disclosed
+    pub body: Policy,
+}
+
+impl HttpPolicy {
+    /// Disclose the body according to `body`, redacting every header value.
+    pub fn new(body: Policy) -> Self {
+        HttpPolicy {
+            headers: HeaderPolicy {
+                default: Disclosure::Redact,
+                rules: Vec::new(),
+                sensitive: ["Set-Cookie", "WWW-Authenticate", "Proxy-Authenticate"]
+                    .map(String::from)
+                    .to_vec(),
+            },
+            body,
+        }
+    }
+
+    /// Reveal the values of the headers named `names`.
+    pub fn reveal_headers(self, names: &[&str]) -> Self {
+        names
+            .iter()
+            .fold(self, |policy, name| policy.header(HeaderRule::reveal(name)))
+    }
+
+    /// Redact the values of the headers named `names`.
+    pub fn redact_headers(self, names: &[&str]) -> Self {
+        names
+            .iter()
+            .fold(self, |policy, name| policy.header(HeaderRule::redact(name)))
+    }
+
+    /// Add a header rule.
+    pub fn header(mut self, rule: HeaderRule) -> Self {
+        self.headers.rules.push(rule);
+        self
+    }
+}
+
+/// Which header values to disclose: a default for headers no rule matches,
+/// plus rules that override it.
+///
+/// When several rules match a header, the most restrictive one wins, so
+/// `Cookie` stays redacted if one rule reveals it and another redacts it.
+/// [`Disclosure::RedactContent`] redacts a value like [`Disclosure::Redact`].
+///
+/// ```
+/// use json_str_redactor::{find_response_ranges, HeaderRule, HttpPolicy, Policy};
+///
+/// let transcript = b"HTTP/1.1 200 OK\r\nContent-Type: text/html\r\n\r\n\"ok\"";
+/// let policy = HttpPolicy::new(Policy::allow(&[Vec::<&str>::new()]))
+///     .header(HeaderRule::reveal("content-type").containing("json"));
+/// let ranges = find_response_ranges(transcript, &policy, &Default::default());
+/// // The value does not contain "json", so it stays redacted
+/// assert_eq!(ranges, [[0, 31], [40, 48]]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeaderPolicy {
+    pub default: Disclosure,
+    pub rules: Vec<HeaderRule>,
+    /// The names of headers redacted whatever `default` says, compared
+    /// case-insensitively. A rule matching such a header still applies.
+    pub sensitive: Vec<String>,
+}
+
+impl HeaderPolicy {
+    // The disclosure of the value of the header `name`
+    fn disclosure(&self, name: &[u8], value: &[u8]) -> Disclosure {
+        self.rules
+            .iter()
+            .filter(|rule| rule.matches(name, value))
+            .map(|rule| rule.disclosure)
+            .max()
+            .unwrap_or_else(|| {
+                if self
+                    .sensitive
+                    .iter()
+                    .any(|sensitive| sensitive.as_bytes().eq_ignore_ascii_case(name))
+                {
+                    Disclosure::Redact
+                } else {
+                    self.default
+                }
+            })
+    }
+}
+
+/// A header name, compared case-insensitively, and the disclosure of the
+/// values of the headers it names.
+///
+/// A rule with `contains` set only matches headers whose value contains it,
+/// compared case-sensitively.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeaderRule {
+    pub name: String,
+    pub contains: Option<String>,
+    pub disclosure: Disclosure,
+}
+
+impl HeaderRule {
+    /// Reveal the values of the headers named `name`.
+    pub fn reveal(name: &str) -> Self {
+        HeaderRule {
+            name: name.to_string(),
+            contains: None,
+            disclosure: Disclosure::Reveal,
+        }
+    }
+
+    /// Redact the values of the headers named `name`.
+    pub fn redact(name: &str) -> Self {
+        HeaderRule {
+            name: name.to_string(),
+            contains: None,
+            disclosure: Disclosure::Redact,
+        }
+    }
+
+    /// Only match headers whose value contains `needle`.
+    pub fn containing(mut self, needle: &str) -> Self {
+        self.contains = Some(needle.to_string());
+        self
+    }
+
+    fn matches(&self, name: &[u8], value: &[u8]) -> bool {
+        self.name.as_bytes().eq_ignore_ascii_case(name)
+            && self.contains.as_ref().is_none_or(|needle| {
+                needle.is_empty()
+                    || value
+                        .windows(needle.len())
+                        .any(|window| window == needle.as_bytes())
+            })
+    }
+}
+
+/// An error in a malformed HTTP/1.1 transcript, at byte offset `position`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpError {
+    pub position: usize,
+    pub kind: HttpErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HttpErrorKind {
+    /// A start line that is not an HTTP/1.x status or request line
+    InvalidStartLine,
+    /// A header line without a valid name and colon, a folded line, or a
+    /// value holding control characters
+    InvalidHeader,
+    /// No empty line ending the headers
+    UnterminatedHead,
+    /// A `Content-Length` that is not a number, several that disagree, or
+    /// one alongside `Transfer-Encoding`
+    InvalidContentLength,
+    /// A body shorter than its `Content-Length`, or a chunked body cut short
+    IncompleteBody,
+    /// A chunk size line that is not a hexadecimal size with optional
+    /// extensions, or chunk data not followed by a line ending
+    InvalidChunk,
+    /// A `Transfer-Encoding` or `Content-Encoding` the body cannot be read
+    /// through
+    UnsupportedEncoding,
+    /// A body that is not valid UTF-8
+    InvalidUtf8,
+    /// A malformed JSON body, with the position, line and column of the error
+    /// counted in the transcript
+    Body(ParseError),
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            HttpErrorKind::InvalidStartLine => write!(f, "invalid start line")?,
+            HttpErrorKind::InvalidHeader => write!(f, "invalid header")?,
+            HttpErrorKind::UnterminatedHead => write!(f, "headers not terminated")?,
+            HttpErrorKind::InvalidContentLength => write!(f, "invalid content length")?,
+            HttpErrorKind::IncompleteBody => write!(f, "incomplete body")?,
+            HttpErrorKind::InvalidChunk => write!(f, "invalid chunk")?,
+            HttpErrorKind::UnsupportedEncoding => write!(f, "unsupported encoding")?,
+            HttpErrorKind::InvalidUtf8 => write!(f, "body is not valid UTF-8")?,
+            HttpErrorKind::Body(error) => return write!(f, "malformed JSON body: {error}"),
+        }
+        write!(f, " at offset {}", self.position)
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+// A header line located by the byte offsets of its name and of its value
+// without the surrounding whitespace
+#[derive(Debug)]
+pub(crate) struct Header {
+    pub name: [usize; 2],
+    pub value: [usize; 2],
+}
+
+// The parts of an HTTP message: the start line without its line ending, each
+// header line, including the trailer fields of a chunked body, and the body
+#[derive(Debug)]
+pub(crate) struct Message {
+    pub start_line: [usize; 2],
+    pub headers: Vec<Header>,
+    pub body: Body,
+}
+
+// The spans of the transcript holding the content of a body, one per chunk
+// of a chunked body, along with the end of the message and the first error
+// in its framing. Nothing of a malformed or truncated body is located past
+// the error.
+#[derive(Debug)]
+pub(crate) struct Body {
+    pub spans: Vec<[usize; 2]>,
+    pub end: usize,
+    pub error: Option<HttpError>,
+}
+
+/// Find the ranges of an HTTP/1.1 response `transcript` to keep so that it is
+/// disclosed according to `policy`. Returned ranges are sorted and merged
+/// `[start, end)` byte offsets into `transcript`, whatever `options.unit`
+/// says; the other options apply to the JSON body.
+///
+/// A chunked body is decoded before applying the body policy, and the ranges
+/// within it are mapped back to the chunk data, with the chunk size lines
+/// kept as framing.
+///
+/// A transcript with malformed headers discloses nothing, while the body is
+/// handled like [`find_policy_ranges`] handles a document, disclosing it up to
+/// its first error, invalid UTF-8 byte or malformed chunk. Bytes past the end
+/// of the message are never disclosed. With `options.strict`, any error
+/// discloses nothing.
+pub fn find_response_ranges(
+    transcript: &[u8],
+    policy: &HttpPolicy,
+    options: &Options,
+) -> Vec<[usize; 2]> {
+    if options.strict || options.duplicates == DuplicateKeys::Error {
+        return try_find_response_ranges(transcript, policy, options).unwrap_or_default();
+    }
+    let Ok(response) = parse_response(transcript) else {
+        return Vec::new();
+    };
+    let ranges = body_ranges(transcript, &response.body, &policy.body, options);
+    message_ranges(transcript, &response, &policy.headers, Vec::new(), ranges)
+}
+
+/// Like [`find_response_ranges`], but fails on a malformed transcript or JSON
+/// body with the first error in it.
+///
+/// ```
+/// use json_str_redactor::{try_find_response_ranges, HttpPolicy, Policy};
+///
+/// let transcript = b"HTTP/1.1 200 OK\r\nDate: today\r\nContent-Length: 9\r\n\r\n{\"a\":\"b\"}";
+/// let policy = HttpPolicy::new(Policy::allow(&[vec!["a"]])).reveal_headers(&["date"]);
+/// let ranges = try_find_response_ranges(transcript, &policy, &Default::default()).unwrap();
+/// // The value of `Content-Length` is redacted
+/// assert_eq!(ranges, [[0, 46], [47, 60]]);
+/// ```
+pub fn try_find_response_ranges(
+    transcript: &[u8],
+    policy: &HttpPolicy,
+    options: &Options,
+) -> Result<Vec<[usize; 2]>, HttpError> {
+    let response = parse_response(transcript)?;
+    let ranges = try_body_ranges(transcript, &response.body, &policy.body, options)?;
+    Ok(message_ranges(
+        transcript,
+        &response,
+        &policy.headers,
+        Vec::new(),
+        ranges,
+    ))
+}
+
+// The ranges of the content of `body` disclosed by `policy`, up to the first
+// error in it
+pub(crate) fn body_ranges(
+    transcript: &[u8],
+    body: &Body,
+    policy: &Policy,
+    options: &Options,
+) -> Vec<[usize; 2]> {
+    let (text, _) = body_text(transcript, body);
+    find_policy_ranges(&text, policy, &body_options(options))
+}
+
+// Like `body_ranges`, but fails on the first error in `body`. An empty body,
+// as in a `204 No Content` response, is not a malformed document.
+pub(crate) fn try_body_ranges(
+    transcript: &[u8],
+    body: &Body,
+    policy: &Policy,
+    options: &Options,
+) -> Result<Vec<[usize; 2]>, HttpError> {
+    if let Some(error) = &body.error {
+        return Err(error.clone());
+    }
+    if body.spans.iter().all(|span| span[0] == span[1]) {
+        return Ok(Vec::new());
+    }
+    let (text, error) = body_text(transcript, body);
+    if let Some(error) = error {
+        return Err(error);
+    }
+    try_find_policy_ranges(&text, policy, &body_options(options)).map_err(|error| {
+        let position = transcript_offset(&body.spans, error.position);
+        // Count lines and columns in the transcript, like the position
+        let before = &transcript[..position];
+        let line_start = before
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |newline| newline + 1);
+        HttpError {
+            position,
+            kind: HttpErrorKind::Body(ParseError {
+                position,
+                line: before.iter().filter(|&&b| b == b'\n').count() + 1,
+                column: String::from_utf8_lossy(&before[line_start..])
+                    .chars()
+                    .count()
+                    + 1,
+                kind: error.kind,
+            }),
+        }
+    })
+}
+
+// Body ranges are shifted into the transcript, which is counted in bytes
+fn body_options(options: &Options) -> Options {
+    Options {
+        unit: OffsetUnit::Utf8,
+        ..options.clone()
+    }
+}
+
+// The content of `body`, up to its first invalid UTF-8 byte, and the error at
+// that byte
+fn body_text(transcript: &[u8], body: &Body) -> (String, Option<HttpError>) {
+    let content: Vec<u8> = body
+        .spans
+        .iter()
+        .flat_map(|&[start, end]| &transcript[start..end])
+        .copied()
+        .collect();
+    match String::from_utf8(content) {
+        Ok(text) => (text, None),
+        Err(error) => {
+            let valid = error.utf8_error().valid_up_to();
+            let mut content = error.into_bytes();
+            content.truncate(valid);
+            let error = HttpError {
+                position: transcript_offset(&body.spans, valid),
+                kind: HttpErrorKind::InvalidUtf8,
+            };
+            (String::from_utf8(content).unwrap_or_default(), Some(error))
+        }
+    }
+}
+
+// The transcript offset of `offset` into the content spread over `spans`
+fn transcript_offset(spans: &[[usize; 2]], mut offset: usize) -> usize {
+    for &[start, end] in spans {
+        if offset < end - start {
+            return start + offset;
+        }
+        offset -= end - start;
+    }
+    spans.last().map_or(0, |span| span[1]) + offset
+}
+
+// Map `ranges` over the content spread over `spans` to the transcript,
+// splitting them where the content is interrupted by chunk framing
+fn map_ranges(spans: &[[usize; 2]], ranges: &[[usize; 2]]) -> Vec<[usize; 2]> {
+    let mut mapped = Vec::new();
+    let mut offset = 0;
+    for &[start, end] in spans {
+        let len = end - start;
+        for &[range_start, range_end] in ranges {
+            let from = range_start.max(offset);
+            let to = range_end.min(offset + len);
+            if from < to {
+                mapped.push([start + from - offset, start + to - offset]);
+            }
+        }
+        offset += len;
+    }
+    mapped
+}
+
+// Keep the framing of `message` outside the ranges in `hidden`, the header
+// values revealed by `headers`, and `body_ranges` mapped to the content of
+// the body
+pub(crate) fn message_ranges(
+    transcript: &[u8],
+    message: &Message,
+    headers: &HeaderPolicy,
+    mut hidden: Vec<[usize; 2]>,
+    body_ranges: Vec<[usize; 2]>,
+) -> Vec<[usize; 2]> {
+    hidden.extend(
+        message
+            .headers
+            .iter()
+            .filter(|header| {
+                let name = &transcript[header.name[0]..header.name[1]];
+                let value = &transcript[header.value[0]..header.value[1]];
+                headers.disclosure(name, value) != Disclosure::Reveal
+            })
+            .map(|header| header.value)
+            .chain(message.body.spans.iter().copied()),
+    );
+    hidden.sort_unstable();
+
+    let mut kept = Vec::new();
+    let mut cursor = 0;
+    for [start, end] in hidden {
+        kept.push([cursor, start]);
+        cursor = end;
+    }
+    kept.push([cursor, message.body.end]);
+    kept.extend(map_ranges(&message.body.spans, &body_ranges));
+    merge_ranges(kept)
+}
+
+// Parse the head of an HTTP/1.x response
+pub(crate) fn parse_response(transcript: &[u8]) -> Result<Message, HttpError> {
+    let message = parse_message(transcript, false)?;
+    let [start, end] = message.start_line;
+    if !is_status_line(&transcript[start..end]) {
+        return Err(HttpError {
+            position: start,
+            kind: HttpErrorKind::InvalidStartLine,
+        });
+    }
+    Ok(message)
+}
+
+// `HTTP/1.x SP 3DIGIT [SP reason]`, with no control characters but tabs in
+// the reason, so that a bare LF cannot fold a header into the kept line
+fn is_status_line(line: &[u8]) -> bool {
+    let is_digit = |i: usize| line.get(i).is_some_and(u8::is_ascii_digit);
+    line.starts_with(b"HTTP/1.")
+        && is_digit(7)
+        && line.get(8) == Some(&b' ')
+        && (9..12).all(is_digit)
+        && (line.len() == 12 || line[12] == b' ')
+        && line[12.min(line.len())..]
+            .iter()
+            .all(|&b| (b >= b' ' || b == b'\t') && b != 0x7f)
+}
+
+// Split `transcript` into its start line, headers and body, checking the
+// header lines and the framing headers. The body of a response without
+// framing headers runs to the end of the transcript, while a request has none.
+pub(crate) fn parse_message(transcript: &[u8], request: bool) -> Result<Message, HttpError> {
+    let head_end = transcript
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or(HttpError {
+            position: transcript.len(),
+            kind: HttpErrorKind::UnterminatedHead,
+        })?;
+    let mut lines = Vec::new();
+    let mut start = 0;
+    while start < head_end + 2 {
+        let end = line_end(transcript, start).unwrap_or(head_end);
+        lines.push([start, end]);
+        start = end + 2;
+    }
+
+    let body_start = head_end + 4;
+    let mut content_length = None;
+    let mut chunked = None;
+    let mut headers = Vec::new();
+    for &[start, end] in &lines[1..] {
+        let header = parse_header(transcript, start, end)?;
+        let name = &transcript[header.name[0]..header.name[1]];
+        let value = &transcript[header.value[0]..header.value[1]];
+        let error = |kind| HttpError {
+            position: header.value[0],
+            kind,
+        };
+        if name.eq_ignore_ascii_case(b"content-length") {
+            let length = std::str::from_utf8(value)
+                .ok()
+                .filter(|value| !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()))
+                .and_then(|value| value.parse::<usize>().ok())
+                .ok_or(error(HttpErrorKind::InvalidContentLength))?;
+            if content_length.is_some_and(|(previous, _)| previous != length) {
+                return Err(error(HttpErrorKind::InvalidContentLength));
+            }
+            content_length = Some((length, header.value[0]));
+        } else if name.eq_ignore_ascii_case(b"transfer-encoding") {
+            // Only a body chunked once and not otherwise encoded can be read
+            if chunked.is_some() || !value.eq_ignore_ascii_case(b"chunked") {
+                return Err(error(HttpErrorKind::UnsupportedEncoding));
+            }
+            chunked = Some(header.value[0]);
+        } else if name.eq_ignore_ascii_case(b"content-encoding")
+            && !value.eq_ignore_ascii_case(b"identity")
+        {
+            return Err(error(HttpErrorKind::UnsupportedEncoding));
+        }
+        headers.push(header);
+    }
+
+    let body = match (content_length, chunked) {
+        // Framing that parsers may disagree on
+        (Some((_, position)), Some(_)) => {
+            return Err(HttpError {
+                position,
+                kind: HttpErrorKind::InvalidContentLength,
+            })
+        }
+        (Some((length, _)), None) => {
+            let end = body_start.saturating_add(length);
+            Body {
+                spans: vec![[body_start, end.min(transcript.len())]],
+                end: end.min(transcript.len()),
+                error: (end > transcript.len()).then_some(HttpError {
+                    position: transcript.len(),
+                    kind: HttpErrorKind::IncompleteBody,
+                }),
+            }
+        }
+        (None, Some(_)) => {
+            let mut spans = Vec::new();
+            match parse_chunks(transcript, body_start, &mut spans, &mut headers) {
+                Ok(end) => Body {
+                    spans,
+                    end,
+                    error: None,
+                },
+                Err(error) => Body {
+                    spans,
+                    end: error.position,
+                    error: Some(error),
+                },
+            }
+        }
+        (None, None) if request => Body {
+            spans: Vec::new(),
+            end: body_start,
+            error: None,
+        },
+        (None, None) => Body {
+            spans: vec![[body_start, transcript.len()]],
+            end: transcript.len(),
+            error: None,
+        },
+    };
+    Ok(Message {
+        start_line: lines[0],
+        headers,
+        body,
+    })
+}
+
+// Parse a chunked body starting at `start`, pushing the spans of the chunk
+// data to `spans` and the trailer fields to `headers`, and return the end of
+// the message
+fn parse_chunks(
+    transcript: &[u8],
+    mut start: usize,
+    spans: &mut Vec<[usize; 2]>,
+    headers: &mut Vec<Header>,
+) -> Result<usize, HttpError> {
+    let incomplete = HttpError {
+        position: transcript.len(),
+        kind: HttpErrorKind::IncompleteBody,
+    };
+    let invalid = |position| HttpError {
+        position,
+        kind: HttpErrorKind::InvalidChunk,
+    };
+    loop {
+        let end = line_end(transcript, start).ok_or(incomplete.clone())?;
+        let size = chunk_size(&transcript[start..end]).ok_or(invalid(start))?;
+        let data = end + 2;
+        if size == 0 {
+            start = data;
+            break;
+        }
+        let data_end = data.saturating_add(size);
+        spans.push([data, data_end.min(transcript.len())]);
+        match transcript.get(data_end..data_end.saturating_add(2)) {
+            Some(b"\r\n") => start = data_end + 2,
+            Some(_) => return Err(invalid(data_end)),
+            None if transcript.get(data_end).is_some_and(|&b| b != b'\r') => {
+                return Err(invalid(data_end))
+            }
+            None => return Err(incomplete),
+        }
+    }
+    // Trailer fields, up to an empty line
+    loop {
+        let end = line_end(transcript, start).ok_or(incomplete.clone())?;
+        if end == start {
+            return Ok(end + 2);
+        }
+        headers.push(parse_header(transcript, start, end)?);
+        start = end + 2;
+    }
+}
+
+// `1*HEXDIG [ BWS ";" chunk-ext ]`
+fn chunk_size(line: &[u8]) -> Option<usize> {
+    let digits = line.iter().take_while(|b| b.is_ascii_hexdigit()).count();
+    let size = usize::from_str_radix(std::str::from_utf8(&line[..digits]).ok()?, 16).ok()?;
+    let extensions = line[digits..].trim_ascii_start();
+    let valid = (extensions.is_empty() || extensions[0] == b';')
+        && extensions
+            .iter()
+            .all(|&b| (b >= b' ' || b == b'\t') && b != 0x7f);
+    valid.then_some(size)
+}
+
+// The offset of the next CRLF from `start`
+fn line_end(transcript: &[u8], start: usize) -> Option<usize> {
+    transcript
+        .get(start..)?
+        .windows(2)
+        .position(|window| window == b"\r\n")
+        .map(|offset| start + offset)
+}
+
+// Parse `name ":" OWS value OWS`, with `name` made of token chars and `value`
+// of visible chars, spaces and tabs
+fn parse_header(transcript: &[u8], start: usize, end: usize) -> Result<Header, HttpError> {
+    let line = &transcript[start..end];
+    let invalid = |offset: usize| HttpError {
+        position: start + offset,
+        kind: HttpErrorKind::InvalidHeader,
+    };
+    let colon = line.iter().position(|&b| b == b':').ok_or(invalid(0))?;
+    if let Some(offset) = line[..colon].iter().position(|&b| !is_token_char(b)) {
+        return Err(invalid(offset));
+    }
+    if colon == 0 {
+        return Err(invalid(0));
+    }
+    if let Some(offset) = line[colon + 1..]
+        .iter()
+        .position(|&b| (b < b' ' && b != b'\t') || b == 0x7f)
+    {
+        return Err(invalid(colon + 1 + offset));
+    }
+    let is_space = |b: &u8| *b == b' ' || *b == b'\t';
+    let value_start = colon + 1 + line[colon + 1..].iter().take_while(|b| is_space(b)).count();
+    let value_end = line.len()
+        - line[value_start..]
+            .iter()
+            .rev()
+            .take_while(|b| is_space(b))
+            .count();
+    Ok(Header {
+        name: [start, start + colon],
+        value: [start + value_start, start + value_end],
+    })
+}
+
+pub(crate) fn is_token_char(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
@@ -0,0 +1,50 @@
+//! Compute the ranges of a JSON string that should be kept when selectively
+//! disclosing the values under a list of key sequences, while keeping every
+//! key and structural character, the original string length and ordering.
+//!
+//! ```
+//! use json_str_redactor::find_ranges;
+//!
+//! let json = r#"{"name":"Alice","age":30,"contacts":[{"type":"email","value":"alice@email.com"},{"type":"phone","value":"123-456-7890"}],"isActive":true}"#;
+//! let keys = [vec!["contacts", "type"], vec!["name"]];
+//! assert_eq!(
+//!     find_ranges(json, &keys),
+//!     [[0, 22], [24, 61], [78, 104], [118, 132], [136, 137]]
+//! );
+//! ```
+
+mod duplicates;
+mod filter;
+mod http;
+mod matches;
+mod offsets;
+mod parser;
+mod path;
+mod policy;
+mod ranges;
+mod redact;
+mod request;
+mod scanner;
+mod selector;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+pub use duplicates::{DuplicateKey, DuplicateKeys};
+pub use filter::{Comparison, Filter, Literal};
+pub use http::{
+    find_response_ranges, try_find_response_ranges, HeaderPolicy, HeaderRule, HttpError,
+    HttpErrorKind, HttpPolicy,
+};
+pub use matches::{Match, ValueKind};
+pub use offsets::{convert_ranges, OffsetUnit};
+pub use parser::{ParseError, ParseErrorKind, MAX_DEPTH};
+pub use path::{KeySequence, Segment};
+pub use policy::{Disclosure, Part, Policy, Rule};
+pub use ranges::{
+    find_policy_ranges, find_ranges, find_ranges_with, find_report, try_find_policy_ranges,
+    try_find_ranges, try_find_ranges_with, KeyMode, Options, Report, WhitespaceMode,
+};
+pub use redact::{redact_json, redact_json_filled, redact_json_with, PlaceholderStyle};
+pub use request::{find_request_ranges, try_find_request_ranges, RequestPolicy};
+pub use scanner::{Scanner, Token, TokenKind};
+pub use selector::{Selector, SelectorError, SelectorErrorKind};
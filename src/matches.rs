@@ -0,0 +1,107 @@
+use crate::parser::{Node, NodeKind};
+use crate::path::{Matcher, Segment, States, Step};
+
+/// The JSON type of a matched value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ValueKind {
+    Object,
+    Array,
+    String,
+    Number,
+    Bool,
+    Null,
+}
+
+/// A value matched by a rule.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Match {
+    /// The `[start, end)` offsets of the value
+    pub span: [usize; 2],
+    pub kind: ValueKind,
+    /// The path from the root to the value, made of keys and indices
+    pub path: Vec<Segment>,
+}
+
+// Find the values matched by each of the paths of `matcher`, in document
+// order, skipping the members whose keys start at an offset in `shadowed`
+pub(crate) fn find_matches(
+    root: &Node,
+    json: &str,
+    matcher: &Matcher,
+    shadowed: &[usize],
+    paths: usize,
+) -> Vec<Vec<Match>> {
+    let mut finder = Finder {
+        json,
+        matcher,
+        shadowed,
+        path: Vec::new(),
+        matches: vec![Vec::new(); paths],
+    };
+    finder.visit(root, &matcher.root());
+    finder.matches
+}
+
+struct Finder<'a> {
+    json: &'a str,
+    matcher: &'a Matcher,
+    shadowed: &'a [usize],
+    path: Vec<Segment>,
+    matches: Vec<Vec<Match>>,
+}
+
+impl Finder<'_> {
+    fn visit(&mut self, node: &Node, states: &States) {
+        for rule in self.matcher.matched(states) {
+            self.matches[rule].push(Match {
+                span: [node.start, node.end],
+                kind: value_kind(node, self.json),
+                path: self.path.clone(),
+            });
+        }
+        // No path can match below this node
+        if states.is_empty() {
+            return;
+        }
+        match &node.kind {
+            NodeKind::Object(members) => {
+                for member in members {
+                    if self.shadowed.binary_search(&member.key.start).is_ok() {
+                        continue;
+                    }
+                    let states = self.matcher.step(states, Step::Member(&member.name));
+                    self.path.push(Segment::Key(member.name.clone()));
+                    self.visit(&member.value, &states);
+                    self.path.pop();
+                }
+            }
+            NodeKind::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    let step = Step::Element {
+                        index,
+                        len: items.len(),
+                        item,
+                        json: self.json,
+                        shadowed: self.shadowed,
+                    };
+                    let states = self.matcher.step(states, step);
+                    self.path.push(Segment::Index(index as i64));
+                    self.visit(item, &states);
+                    self.path.pop();
+                }
+            }
+            NodeKind::String | NodeKind::Number | NodeKind::Literal => {}
+        }
+    }
+}
+
+fn value_kind(node: &Node, json: &str) -> ValueKind {
+    match node.kind {
+        NodeKind::Object(_) => ValueKind::Object,
+        NodeKind::Array(_) => ValueKind::Array,
+        NodeKind::String => ValueKind::String,
+        NodeKind::Number => ValueKind::Number,
+        NodeKind::Literal if &json[node.start..node.end] == "null" => ValueKind::Null,
+        NodeKind::Literal => ValueKind::Bool,
+    }
+}
@@ -0,0 +1,62 @@
+/// The unit in which range offsets are counted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum OffsetUnit {
+    /// Bytes of the UTF-8 encoding, as used by TLSNotary transcripts
+    #[default]
+    Utf8,
+    /// UTF-16 code units, as used by JavaScript string indices
+    Utf16,
+    /// Unicode scalar values, i.e. Rust `char`s
+    Char,
+}
+
+impl OffsetUnit {
+    fn len_of(self, c: char) -> usize {
+        match self {
+            OffsetUnit::Utf8 => c.len_utf8(),
+            OffsetUnit::Utf16 => c.len_utf16(),
+            OffsetUnit::Char => 1,
+        }
+    }
+}
+
+/// Convert `ranges` over `text` from offsets counted in `from` units to
+/// offsets counted in `to` units.
+///
+/// An offset that falls inside a character (e.g. between the two halves of a
+/// UTF-16 surrogate pair) is moved to the nearest character boundary inside the
+/// range, so a converted range never covers more of `text` than the original.
+/// Ranges that become empty are dropped.
+pub fn convert_ranges(
+    text: &str,
+    ranges: &[[usize; 2]],
+    from: OffsetUnit,
+    to: OffsetUnit,
+) -> Vec<[usize; 2]> {
+    if from == to {
+        return ranges.to_vec();
+    }
+
+    // Every char boundary of `text` counted in both units
+    let mut boundaries = Vec::with_capacity(text.len() + 1);
+    let (mut from_offset, mut to_offset) = (0, 0);
+    boundaries.push((0, 0));
+    for c in text.chars() {
+        from_offset += from.len_of(c);
+        to_offset += to.len_of(c);
+        boundaries.push((from_offset, to_offset));
+    }
+
+    ranges
+        .iter()
+        .filter_map(|&[start, end]| {
+            let first = boundaries.partition_point(|&(offset, _)| offset < start);
+            let last = boundaries
+                .partition_point(|&(offset, _)| offset <= end)
+                .checked_sub(1)?;
+            let start = boundaries.get(first)?.1;
+            let end = boundaries[last].1;
+            (start < end).then_some([start, end])
+        })
+        .collect()
+}
@@ -0,0 +1,383 @@
+use crate::scanner::{
+    decode_string, escape_len, is_json_number, is_terminated_string, Scanner, Token, TokenKind,
+};
+use std::fmt;
+use std::iter::Peekable;
+
+/// An error in a malformed JSON document, at byte offset `position`, which is
+/// on line `line` and column `column` (both counted from 1, with columns in
+/// chars).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub position: usize,
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The document holds no value
+    EmptyDocument,
+    /// A string without its closing quote
+    UnterminatedString,
+    /// An object or array left open at the end of the input, or a closing
+    /// bracket or brace that does not match the open one
+    UnbalancedBrackets,
+    /// A token that cannot appear at this point, such as a missing colon or
+    /// comma, or a bare word other than `true`, `false` and `null`
+    UnexpectedToken,
+    /// A bare word starting with `-` or a digit that is not a JSON number
+    InvalidNumber,
+    /// Something other than whitespace after the root value
+    TrailingData,
+    /// An unescaped control character in a string, only reported in strict
+    /// mode
+    ControlCharacter,
+    /// An unknown escape sequence or an unpaired surrogate in a string, only
+    /// reported in strict mode
+    InvalidEscape,
+    /// A key appearing again in the same object, only reported with
+    /// [`DuplicateKeys::Error`](crate::DuplicateKeys::Error)
+    DuplicateKey,
+    /// Objects and arrays nested more than [`MAX_DEPTH`] levels deep
+    TooDeep,
+}
+
+/// How deeply objects and arrays may be nested. Deeper documents fail with
+/// [`ParseErrorKind::TooDeep`], and nothing of them is disclosed.
+pub const MAX_DEPTH: usize = 128;
+
+impl ParseError {
+    pub(crate) fn new(json: &str, position: usize, kind: ParseErrorKind) -> Self {
+        let before = &json[..position];
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        ParseError {
+            position,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            kind,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::EmptyDocument => write!(f, "empty document")?,
+            ParseErrorKind::UnterminatedString => write!(f, "unterminated string")?,
+            ParseErrorKind::UnbalancedBrackets => write!(f, "unbalanced brackets")?,
+            ParseErrorKind::UnexpectedToken => write!(f, "unexpected token")?,
+            ParseErrorKind::InvalidNumber => write!(f, "invalid number")?,
+            ParseErrorKind::TrailingData => write!(f, "trailing data after the root value")?,
+            ParseErrorKind::ControlCharacter => write!(f, "unescaped control character")?,
+            ParseErrorKind::InvalidEscape => write!(f, "invalid escape sequence")?,
+            ParseErrorKind::DuplicateKey => write!(f, "duplicate key")?,
+            ParseErrorKind::TooDeep => write!(f, "nesting too deep")?,
+        }
+        write!(
+            f,
+            " at line {}, column {} (offset {})",
+            self.line, self.column, self.position
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// A JSON value located by its byte offsets in the source string
+#[derive(Debug)]
+pub(crate) struct Node {
+    pub start: usize,
+    pub end: usize,
+    pub kind: NodeKind,
+}
+
+#[derive(Debug)]
+pub(crate) enum NodeKind {
+    Object(Vec<Member>),
+    Array(Vec<Node>),
+    String,
+    Number,
+    Literal,
+}
+
+// A key/value pair of an object
+#[derive(Debug)]
+pub(crate) struct Member {
+    pub key: Token,
+    pub name: String,
+    pub value: Node,
+}
+
+// A parsed document: the root value plus the end of the region that was
+// successfully parsed, including whitespace around the root, and the first
+// error found
+pub(crate) struct Document {
+    pub root: Option<Node>,
+    pub end: usize,
+    pub error: Option<ParseError>,
+}
+
+// Parse `json` leniently. Parsing stops at the first token that does not fit
+// the grammar; every container that is still open at that point ends there.
+// Strings without a closing quote, invalid numbers and unknown bare words are
+// reported as errors but parsed anyway, so callers must not disclose anything
+// past the first error. In `strict` mode, the contents of strings are checked
+// as well.
+pub(crate) fn parse(json: &str, strict: bool) -> Document {
+    let mut parser = Parser {
+        json,
+        tokens: Scanner::new(json).peekable(),
+        strict,
+        depth: 0,
+        halted: false,
+        error: None,
+    };
+    parser.skip_whitespace();
+    let root = parser.parse_value(None);
+    let end = if parser.halted {
+        root.as_ref().map_or(0, |root| root.end)
+    } else {
+        parser.skip_whitespace();
+        if let Some(&token) = parser.tokens.peek() {
+            let kind = match token.kind {
+                TokenKind::EndObject | TokenKind::EndArray => ParseErrorKind::UnbalancedBrackets,
+                _ => ParseErrorKind::TrailingData,
+            };
+            parser.fail(token.start, kind);
+        }
+        parser.tokens.peek().map_or(json.len(), |token| token.start)
+    };
+    Document {
+        root,
+        end,
+        error: parser.error,
+    }
+}
+
+struct Parser<'a> {
+    json: &'a str,
+    tokens: Peekable<Scanner<'a>>,
+    strict: bool,
+    // Number of objects and arrays open around the next token
+    depth: usize,
+    halted: bool,
+    error: Option<ParseError>,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        self.tokens
+            .next_if(|token| token.kind == TokenKind::Whitespace);
+    }
+
+    // Offset where the next token starts, or the end of the input
+    fn position(&mut self) -> usize {
+        self.tokens
+            .peek()
+            .map_or(self.json.len(), |token| token.start)
+    }
+
+    // Record an error unless an earlier one was found
+    fn fail(&mut self, position: usize, kind: ParseErrorKind) {
+        if self.error.is_none() {
+            self.error = Some(ParseError::new(self.json, position, kind));
+        }
+    }
+
+    // Stop parsing at the next token, which does not fit the grammar inside
+    // the container closed by `close`, or at the root when `close` is `None`
+    fn halt(&mut self, close: Option<TokenKind>) {
+        self.halted = true;
+        let kind = match (self.tokens.peek().map(|token| token.kind), close) {
+            (None, None) => ParseErrorKind::EmptyDocument,
+            (None, Some(_)) => ParseErrorKind::UnbalancedBrackets,
+            (Some(kind @ (TokenKind::EndObject | TokenKind::EndArray)), _)
+                if Some(kind) != close =>
+            {
+                ParseErrorKind::UnbalancedBrackets
+            }
+            (Some(_), _) => ParseErrorKind::UnexpectedToken,
+        };
+        let position = self.position();
+        self.fail(position, kind);
+    }
+
+    // Record an error for a string token without its closing quote, or in
+    // strict mode with a control character or invalid escape in it
+    fn check_string(&mut self, token: Token) {
+        if !is_terminated_string(&self.json[token.start..token.end]) {
+            self.fail(token.start, ParseErrorKind::UnterminatedString);
+            return;
+        }
+        if !self.strict {
+            return;
+        }
+        let bytes = self.json.as_bytes();
+        let mut i = token.start + 1;
+        while i < token.end - 1 {
+            match bytes[i] {
+                b'\\' => match escape_len(&self.json[i..token.end]) {
+                    Some(len) => i += len,
+                    None => return self.fail(i, ParseErrorKind::InvalidEscape),
+                },
+                0x00..=0x1F => return self.fail(i, ParseErrorKind::ControlCharacter),
+                _ => i += 1,
+            }
+        }
+    }
+
+    fn parse_value(&mut self, close: Option<TokenKind>) -> Option<Node> {
+        let Some(token) = self.tokens.next_if(|token| {
+            !matches!(
+                token.kind,
+                TokenKind::EndObject
+                    | TokenKind::EndArray
+                    | TokenKind::Colon
+                    | TokenKind::Comma
+                    | TokenKind::Whitespace
+            )
+        }) else {
+            self.halt(close);
+            return None;
+        };
+        let raw = &self.json[token.start..token.end];
+        if matches!(token.kind, TokenKind::BeginObject | TokenKind::BeginArray) {
+            if self.depth == MAX_DEPTH {
+                self.fail(token.start, ParseErrorKind::TooDeep);
+                self.halted = true;
+                return None;
+            }
+            self.depth += 1;
+            let node = match token.kind {
+                TokenKind::BeginObject => self.parse_object(token),
+                _ => self.parse_array(token),
+            };
+            self.depth -= 1;
+            return Some(node);
+        }
+        let kind = match token.kind {
+            TokenKind::String => {
+                self.check_string(token);
+                NodeKind::String
+            }
+            TokenKind::Number => {
+                if !is_json_number(raw) {
+                    self.fail(token.start, ParseErrorKind::InvalidNumber);
+                }
+                NodeKind::Number
+            }
+            _ => {
+                if !matches!(raw, "true" | "false" | "null") {
+                    self.fail(token.start, ParseErrorKind::UnexpectedToken);
+                }
+                NodeKind::Literal
+            }
+        };
+        Some(Node {
+            start: token.start,
+            end: token.end,
+            kind,
+        })
+    }
+
+    // Consume a comma or the closing `close` token after a container entry,
+    // returning the end of the container once it is closed
+    fn parse_separator(&mut self, close: TokenKind) -> Option<usize> {
+        self.skip_whitespace();
+        if self
+            .tokens
+            .next_if(|token| token.kind == TokenKind::Comma)
+            .is_some()
+        {
+            return None;
+        }
+        match self.tokens.next_if(|token| token.kind == close) {
+            Some(token) => Some(token.end),
+            None => {
+                self.halt(Some(close));
+                Some(self.position())
+            }
+        }
+    }
+
+    fn parse_object(&mut self, open: Token) -> Node {
+        let close = Some(TokenKind::EndObject);
+        let mut members = Vec::new();
+        let end = loop {
+            self.skip_whitespace();
+            if members.is_empty() {
+                if let Some(close) = self
+                    .tokens
+                    .next_if(|token| token.kind == TokenKind::EndObject)
+                {
+                    break close.end;
+                }
+            }
+            let Some(key) = self.tokens.next_if(|token| token.kind == TokenKind::String) else {
+                self.halt(close);
+                break self.position();
+            };
+            self.check_string(key);
+            self.skip_whitespace();
+            if self
+                .tokens
+                .next_if(|token| token.kind == TokenKind::Colon)
+                .is_none()
+            {
+                self.halt(close);
+                break self.position();
+            }
+            self.skip_whitespace();
+            let Some(value) = self.parse_value(close) else {
+                break self.position();
+            };
+            let name = decode_string(&self.json[key.start + 1..key.end - 1]);
+            members.push(Member { key, name, value });
+            if self.halted {
+                break self.position();
+            }
+
+            if let Some(end) = self.parse_separator(TokenKind::EndObject) {
+                break end;
+            }
+        };
+        Node {
+            start: open.start,
+            end,
+            kind: NodeKind::Object(members),
+        }
+    }
+
+    fn parse_array(&mut self, open: Token) -> Node {
+        let mut items = Vec::new();
+        let end = loop {
+            self.skip_whitespace();
+            if items.is_empty() {
+                if let Some(close) = self
+                    .tokens
+                    .next_if(|token| token.kind == TokenKind::EndArray)
+                {
+                    break close.end;
+                }
+            }
+            let Some(item) = self.parse_value(Some(TokenKind::EndArray)) else {
+                break self.position();
+            };
+            items.push(item);
+            if self.halted {
+                break self.position();
+            }
+
+            if let Some(end) = self.parse_separator(TokenKind::EndArray) {
+                break end;
+            }
+        };
+        Node {
+            start: open.start,
+            end,
+            kind: NodeKind::Array(items),
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use crate::filter::Filter;
+use crate::parser::Node;
+
+/// One step of a key sequence.
+///
+/// Arrays are transparent to [`Segment::Key`], so `["contacts", "type"]`
+/// matches the `type` of every element of a `contacts` array, while the other
+/// segments address array elements explicitly, e.g. `["contacts", 0, "type"]`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Segment {
+    /// The object member with this key, compared after decoding escapes
+    Key(String),
+    /// The array element at this index, counting from the end when negative
+    Index(i64),
+    /// The array elements from `start` (inclusive) to `end` (exclusive), with
+    /// negative bounds counting from the end and missing bounds meaning the
+    /// start or end of the array, like a Python slice
+    Slice(Option<i64>, Option<i64>),
+    /// Any array element or object member
+    Wildcard,
+    /// Any number of levels, including none, so that the following segments
+    /// match at any depth below this point
+    Descendants,
+    /// The array elements that pass a [`Filter`]
+    Filter(Filter),
+}
+
+impl From<&str> for Segment {
+    fn from(key: &str) -> Self {
+        Segment::Key(key.to_string())
+    }
+}
+
+impl From<&String> for Segment {
+    fn from(key: &String) -> Self {
+        Segment::Key(key.clone())
+    }
+}
+
+impl From<String> for Segment {
+    fn from(key: String) -> Self {
+        Segment::Key(key)
+    }
+}
+
+impl From<i64> for Segment {
+    fn from(index: i64) -> Self {
+        Segment::Index(index)
+    }
+}
+
+/// A sequence of segments identifying values to disclose: a `Vec` of keys
+/// and/or [`Segment`]s, or a parsed [`Selector`](crate::Selector).
+pub trait KeySequence {
+    fn to_segments(&self) -> Vec<Segment>;
+}
+
+impl<S: Clone + Into<Segment>> KeySequence for Vec<S> {
+    fn to_segments(&self) -> Vec<Segment> {
+        self.iter().cloned().map(Into::into).collect()
+    }
+}
+
+impl KeySequence for crate::Selector {
+    fn to_segments(&self) -> Vec<Segment> {
+        self.segments().to_vec()
+    }
+}
+
+// A step taken from a container into one of its children
+#[derive(Clone, Copy)]
+pub(crate) enum Step<'a> {
+    Member(&'a str),
+    Element {
+        index: usize,
+        len: usize,
+        item: &'a Node,
+        json: &'a str,
+        // Sorted starts of the keys of duplicated members the rules do not
+        // apply to, which filters skip too
+        shadowed: &'a [usize],
+    },
+}
+
+impl Segment {
+    // Whether this segment selects the child reached by `step`. `None` means
+    // the segment does not apply to the container and is carried down as is.
+    fn selects(&self, step: Step) -> Option<bool> {
+        match (self, step) {
+            (Segment::Wildcard, _) => Some(true),
+            (Segment::Descendants, _) => None,
+            (Segment::Key(key), Step::Member(name)) => Some(key == name),
+            (Segment::Key(_), Step::Element { .. }) => None,
+            (_, Step::Member(_)) => Some(false),
+            (Segment::Index(i), Step::Element { index, len, .. }) => {
+                Some(resolve_index(*i, len) == Some(index))
+            }
+            (Segment::Slice(start, end), Step::Element { index, len, .. }) => {
+                let start = start.map_or(0, |start| clamp_index(start, len));
+                let end = end.map_or(len, |end| clamp_index(end, len));
+                Some(start <= index && index < end)
+            }
+            (
+                Segment::Filter(filter),
+                Step::Element {
+                    item,
+                    json,
+                    shadowed,
+                    ..
+                },
+            ) => Some(filter.matches(item, json, shadowed)),
+        }
+    }
+}
+
+pub(crate) fn resolve_index(index: i64, len: usize) -> Option<usize> {
+    let index = if index < 0 { len as i64 + index } else { index };
+    usize::try_from(index).ok().filter(|&index| index < len)
+}
+
+fn clamp_index(index: i64, len: usize) -> usize {
+    let index = if index < 0 { len as i64 + index } else { index };
+    index.clamp(0, len as i64) as usize
+}
+
+// Tracks how far into each target path the traversal has matched. A state is a
+// `(path, position)` pair meaning `paths[path][..position]` matched the way
+// down to the current node.
+pub(crate) struct Matcher {
+    paths: Vec<Vec<Segment>>,
+}
+
+pub(crate) type States = Vec<(usize, usize)>;
+
+impl Matcher {
+    pub fn new(paths: Vec<Vec<Segment>>) -> Self {
+        Matcher { paths }
+    }
+
+    pub fn root(&self) -> States {
+        let mut states = States::new();
+        for path in 0..self.paths.len() {
+            self.push_state(&mut states, (path, 0));
+        }
+        states
+    }
+
+    // The indices of the target paths fully matched at the current node
+    pub fn matched<'a>(&'a self, states: &'a States) -> impl Iterator<Item = usize> + 'a {
+        states
+            .iter()
+            .filter(|&&(path, position)| position == self.paths[path].len())
+            .map(|&(path, _)| path)
+    }
+
+    // The states of the child reached from the current node by `step`
+    pub fn step(&self, states: &States, step: Step) -> States {
+        let mut next = States::new();
+        for &(path, position) in states {
+            let Some(segment) = self.paths[path].get(position) else {
+                continue;
+            };
+            match segment.selects(step) {
+                Some(true) => self.push_state(&mut next, (path, position + 1)),
+                Some(false) => {}
+                None => self.push_state(&mut next, (path, position)),
+            }
+        }
+        next
+    }
+
+    // Add `state` to `states`, along with the state skipping past it when it
+    // sits at a `Descendants` segment, which may match zero levels
+    fn push_state(&self, states: &mut States, state: (usize, usize)) {
+        if states.contains(&state) {
+            return;
+        }
+        states.push(state);
+        let (path, position) = state;
+        if self.paths[path].get(position) == Some(&Segment::Descendants) {
+            self.push_state(states, (path, position + 1));
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use crate::path::{KeySequence, Segment};
+use regex::Regex;
+
+/// How much of a value is disclosed.
+///
+/// Variants are ordered from least to most restrictive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Disclosure {
+    /// The value is kept as is
+    Reveal,
+    /// Only the quotes of strings are kept, showing that the value is a
+    /// string and how long it is but not what it says; other values are
+    /// redacted
+    RedactContent,
+    /// The value is redacted
+    Redact,
+}
+
+/// A key sequence and the disclosure of the values it matches.
+///
+/// A revealing rule with a `part` only reveals that part of each string,
+/// number or literal it matches, along with the quotes of strings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rule {
+    pub path: Vec<Segment>,
+    pub disclosure: Disclosure,
+    pub part: Option<Part>,
+}
+
+/// The part of a scalar value revealed by a [`Rule`].
+///
+/// Parts are measured in chars of the value after decoding escapes, so that
+/// `"caf\u00e9"` ends with the single char `é`; the revealed range covers the
+/// escape sequences those chars were written with.
+#[derive(Clone, Debug)]
+pub enum Part {
+    /// The first `n` chars
+    Prefix(usize),
+    /// The last `n` chars
+    Suffix(usize),
+    /// The chars from `start` (inclusive) to `end` (exclusive)
+    Chars(usize, usize),
+    /// The first match of the regex, or of its first capture group when it
+    /// has one. Nothing is revealed when the regex does not match.
+    Capture(Regex),
+}
+
+impl PartialEq for Part {
+    fn eq(&self, other: &Part) -> bool {
+        match (self, other) {
+            (Part::Prefix(a), Part::Prefix(b)) | (Part::Suffix(a), Part::Suffix(b)) => a == b,
+            (Part::Chars(a, b), Part::Chars(c, d)) => (a, b) == (c, d),
+            (Part::Capture(a), Part::Capture(b)) => a.as_str() == b.as_str(),
+            _ => false,
+        }
+    }
+}
+
+impl Part {
+    /// The first match of `pattern`, or of its first capture group.
+    pub fn capture(pattern: &str) -> Result<Part, regex::Error> {
+        Regex::new(pattern).map(Part::Capture)
+    }
+
+    // The `[start, end)` indices into `chars` of the chars to reveal, given
+    // each decoded char with its span in the raw text
+    pub(crate) fn select(&self, chars: &[(char, usize, usize)]) -> Option<[usize; 2]> {
+        let len = chars.len();
+        let [start, end] = match self {
+            Part::Prefix(n) => [0, (*n).min(len)],
+            Part::Suffix(n) => [len.saturating_sub(*n), len],
+            Part::Chars(start, end) => [(*start).min(len), (*end).min(len)],
+            Part::Capture(regex) => {
+                let decoded: String = chars.iter().map(|&(c, _, _)| c).collect();
+                let captures = regex.captures(&decoded)?;
+                let found = captures.get(1).or_else(|| captures.get(0))?;
+                // Convert byte offsets in `decoded` to char indices
+                let index = |offset: usize| decoded[..offset].chars().count();
+                [index(found.start()), index(found.end())]
+            }
+        };
+        (start < end).then_some([start, end])
+    }
+}
+
+/// Which values of a document to disclose: a default for values no rule
+/// matches, plus rules that override it.
+///
+/// A value is disclosed according to the rule that matches it or its closest
+/// enclosing value, so a rule on `$.user.ssn` takes precedence over one on
+/// `$.user`. When rules with different disclosures match the same value, the
+/// most restrictive one wins, with revealing part of a value counting as more
+/// restrictive than revealing all of it and the last rule added winning ties.
+/// Keys and structural characters are always kept.
+///
+/// ```
+/// use json_str_redactor::{find_policy_ranges, redact_json, Options, Policy, Selector};
+///
+/// let json = r#"{"user":{"name":"Alice","ssn":"123"},"token":"abc"}"#;
+/// let policy = Policy::deny(&[Selector::parse("$..ssn").unwrap()]);
+/// let ranges = find_policy_ranges(json, &policy, &Options::default());
+/// assert_eq!(
+///     redact_json(json, ranges),
+///     r#"{"user":{"name":"Alice","ssn":"<REDACTED>"},"token":"abc"}"#
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Policy {
+    pub default: Disclosure,
+    pub rules: Vec<Rule>,
+}
+
+impl Policy {
+    /// Reveal only the values matched by `target_keys_list`, like
+    /// [`find_ranges`](crate::find_ranges).
+    pub fn allow<K: KeySequence>(target_keys_list: &[K]) -> Self {
+        Policy {
+            default: Disclosure::Redact,
+            rules: Vec::new(),
+        }
+        .reveal(target_keys_list)
+    }
+
+    /// Reveal every value except those matched by `target_keys_list`.
+    pub fn deny<K: KeySequence>(target_keys_list: &[K]) -> Self {
+        Policy {
+            default: Disclosure::Reveal,
+            rules: Vec::new(),
+        }
+        .redact(target_keys_list)
+    }
+
+    /// Add rules revealing the values matched by `target_keys_list`.
+    pub fn reveal<K: KeySequence>(self, target_keys_list: &[K]) -> Self {
+        self.with_rules(target_keys_list, Disclosure::Reveal)
+    }
+
+    /// Add rules revealing only `part` of the values matched by
+    /// `target_keys_list`.
+    ///
+    /// ```
+    /// use json_str_redactor::{find_policy_ranges, redact_json, Options, Part, Policy};
+    ///
+    /// let json = r#"{"phone":"123-456-7890"}"#;
+    /// let policy = Policy::allow(&[vec!["x"]]).reveal_part(&[vec!["phone"]], Part::Suffix(4));
+    /// let ranges = find_policy_ranges(json, &policy, &Options::default());
+    /// assert_eq!(ranges, [[0, 10], [18, 24]]);
+    /// ```
+    pub fn reveal_part<K: KeySequence>(mut self, target_keys_list: &[K], part: Part) -> Self {
+        self.rules.extend(target_keys_list.iter().map(|keys| Rule {
+            path: keys.to_segments(),
+            disclosure: Disclosure::Reveal,
+            part: Some(part.clone()),
+        }));
+        self
+    }
+
+    /// Add rules redacting the contents of the strings matched by
+    /// `target_keys_list` but keeping their quotes.
+    pub fn redact_content<K: KeySequence>(self, target_keys_list: &[K]) -> Self {
+        self.with_rules(target_keys_list, Disclosure::RedactContent)
+    }
+
+    /// Add rules redacting the values matched by `target_keys_list`.
+    pub fn redact<K: KeySequence>(self, target_keys_list: &[K]) -> Self {
+        self.with_rules(target_keys_list, Disclosure::Redact)
+    }
+
+    fn with_rules<K: KeySequence>(
+        mut self,
+        target_keys_list: &[K],
+        disclosure: Disclosure,
+    ) -> Self {
+        self.rules.extend(target_keys_list.iter().map(|keys| Rule {
+            path: keys.to_segments(),
+            disclosure,
+            part: None,
+        }));
+        self
+    }
+}
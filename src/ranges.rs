@@ -0,0 +1,524 @@
+use crate::duplicates::{find_duplicates, DuplicateKey, DuplicateKeys};
+use crate::matches::{find_matches, Match};
+use crate::offsets::{convert_ranges, OffsetUnit};
+use crate::parser::{parse, Node, NodeKind, ParseError, ParseErrorKind};
+use crate::path::{KeySequence, Matcher, States, Step};
+use crate::policy::{Disclosure, Part, Policy};
+use crate::scanner::{decode_chars, is_whitespace};
+
+/// Options controlling how [`find_ranges_with`] computes ranges.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    /// Unit of the returned offsets, UTF-8 bytes by default
+    pub unit: OffsetUnit,
+    /// Which object keys are kept, all of them by default
+    pub keys: KeyMode,
+    /// Which whitespace between tokens is kept, all of it by default
+    pub whitespace: WhitespaceMode,
+    /// Whether `json` must be valid RFC 8259 JSON. Any error, including
+    /// control characters and invalid escapes in strings, makes the
+    /// `find_*` functions disclose nothing and the `try_find_*` functions
+    /// fail.
+    pub strict: bool,
+    /// Which occurrence of a duplicated key is disclosed, all of them by
+    /// default. With [`DuplicateKeys::Error`], a duplicate makes the `find_*`
+    /// functions disclose nothing and the `try_find_*` functions fail.
+    pub duplicates: DuplicateKeys,
+}
+
+/// How object keys outside the disclosed values are treated.
+///
+/// A member counts as disclosed when its value, or anything inside it, is
+/// revealed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum KeyMode {
+    /// Keep every key
+    #[default]
+    Reveal,
+    /// Redact the keys of undisclosed members, keeping the colons and commas
+    /// so the number of members is still visible
+    Redact,
+    /// Redact undisclosed members entirely, along with the commas separating
+    /// them from their siblings, so the number of members is hidden
+    HideEntries,
+}
+
+/// How whitespace between tokens, such as the indentation of pretty-printed
+/// JSON, is treated. Whitespace inside strings is part of their value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum WhitespaceMode {
+    /// Keep all whitespace, except within members hidden by
+    /// [`KeyMode::HideEntries`]
+    #[default]
+    Reveal,
+    /// Redact all whitespace
+    Redact,
+    /// Keep only the whitespace inside revealed values, and not inside
+    /// values redacted within them
+    Disclosed,
+}
+
+/// Find the ranges of `json` to keep so that only the values under one of the
+/// key sequences in `target_keys_list` are disclosed, along with every key and
+/// structural character. The document may be any JSON value, with an empty key
+/// sequence matching the whole of it. Returned ranges are sorted and merged
+/// `[start, end)` byte offsets into the UTF-8 encoding of `json`, always on
+/// char boundaries.
+///
+/// Malformed input is handled leniently: values are disclosed up to the first
+/// point where `json` stops making sense, and nothing after it. Use
+/// [`try_find_ranges`] to reject it instead.
+pub fn find_ranges<K: KeySequence>(json: &str, target_keys_list: &[K]) -> Vec<[usize; 2]> {
+    find_ranges_with(json, target_keys_list, &Options::default())
+}
+
+/// Like [`find_ranges`], with offsets counted in `options.unit`.
+pub fn find_ranges_with<K: KeySequence>(
+    json: &str,
+    target_keys_list: &[K],
+    options: &Options,
+) -> Vec<[usize; 2]> {
+    find_policy_ranges(json, &Policy::allow(target_keys_list), options)
+}
+
+/// Find the ranges of `json` to keep so that values are disclosed according to
+/// `policy`, along with every key and structural character.
+pub fn find_policy_ranges(json: &str, policy: &Policy, options: &Options) -> Vec<[usize; 2]> {
+    let analysis = analyze(json, policy, options, false);
+    let too_deep = analysis
+        .error
+        .as_ref()
+        .is_some_and(|error| error.kind == ParseErrorKind::TooDeep);
+    if too_deep || analysis.duplicate.is_some() || (options.strict && analysis.error.is_some()) {
+        return Vec::new();
+    }
+    analysis.ranges
+}
+
+/// Like [`find_ranges`], but fails on malformed JSON with the first error in
+/// it.
+///
+/// ```
+/// use json_str_redactor::{try_find_ranges, ParseErrorKind};
+///
+/// let error = try_find_ranges("{\"a\":\n  \"unclosed}", &[vec!["a"]]).unwrap_err();
+/// assert_eq!(error.kind, ParseErrorKind::UnterminatedString);
+/// assert_eq!((error.position, error.line, error.column), (8, 2, 3));
+/// ```
+pub fn try_find_ranges<K: KeySequence>(
+    json: &str,
+    target_keys_list: &[K],
+) -> Result<Vec<[usize; 2]>, ParseError> {
+    try_find_ranges_with(json, target_keys_list, &Options::default())
+}
+
+/// Like [`find_ranges_with`], but fails on malformed JSON. Only the structure,
+/// numbers and bare words are checked unless `options.strict` is set.
+pub fn try_find_ranges_with<K: KeySequence>(
+    json: &str,
+    target_keys_list: &[K],
+    options: &Options,
+) -> Result<Vec<[usize; 2]>, ParseError> {
+    try_find_policy_ranges(json, &Policy::allow(target_keys_list), options)
+}
+
+/// Like [`find_policy_ranges`], but fails on malformed JSON.
+pub fn try_find_policy_ranges(
+    json: &str,
+    policy: &Policy,
+    options: &Options,
+) -> Result<Vec<[usize; 2]>, ParseError> {
+    find_report(json, policy, options).map(|report| report.ranges)
+}
+
+/// The ranges found by [`find_report`], with what else was learned about the
+/// document while finding them. Offsets are counted in the unit of the
+/// options.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    /// The ranges to keep, as returned by [`try_find_policy_ranges`]
+    pub ranges: Vec<[usize; 2]>,
+    /// The ranges left out, between and around `ranges`
+    pub redacted: Vec<[usize; 2]>,
+    /// The values matched by each rule of the policy, in the order of
+    /// `policy.rules` and then of the document. A rule that matched nothing
+    /// has no values.
+    pub matches: Vec<Vec<Match>>,
+    /// The keys appearing more than once in an object, and which occurrence
+    /// the rules were applied to
+    pub duplicates: Vec<DuplicateKey>,
+}
+
+/// Like [`try_find_policy_ranges`], also reporting the redacted ranges, what
+/// each rule matched and the duplicate keys of the document.
+///
+/// ```
+/// use json_str_redactor::{find_report, Policy, Segment, Selector, ValueKind};
+///
+/// let json = r#"{"ids":[7,8],"name":"x"}"#;
+/// let policy = Policy::allow(&[Selector::parse("$.ids[*]").unwrap()]);
+/// let report = find_report(json, &policy, &Default::default()).unwrap();
+/// assert_eq!(report.ranges, [[0, 20], [23, 24]]);
+/// assert_eq!(report.redacted, [[20, 23]]);
+/// let second = &report.matches[0][1];
+/// assert_eq!(second.span, [10, 11]);
+/// assert_eq!(second.kind, ValueKind::Number);
+/// assert_eq!(second.path, [Segment::from("ids"), Segment::Index(1)]);
+/// ```
+///
+/// ```
+/// use json_str_redactor::{find_report, DuplicateKeys, Options, Policy};
+///
+/// let json = r#"{"to":"alice","to":"mallory"}"#;
+/// let options = Options {
+///     duplicates: DuplicateKeys::Last,
+///     ..Default::default()
+/// };
+/// let report = find_report(json, &Policy::allow(&[vec!["to"]]), &options).unwrap();
+/// assert_eq!(report.ranges, [[0, 6], [13, 29]]);
+/// assert_eq!(report.duplicates[0].keys, [[1, 5], [14, 18]]);
+/// assert_eq!(report.duplicates[0].kept, Some(1));
+/// ```
+pub fn find_report(json: &str, policy: &Policy, options: &Options) -> Result<Report, ParseError> {
+    let analysis = analyze(json, policy, options, true);
+    match analysis.error.or(analysis.duplicate) {
+        Some(error) => Err(error),
+        None => Ok(Report {
+            ranges: analysis.ranges,
+            redacted: analysis.redacted,
+            matches: analysis.matches,
+            duplicates: analysis.duplicates,
+        }),
+    }
+}
+
+// Everything learned about a document
+struct Analysis {
+    ranges: Vec<[usize; 2]>,
+    redacted: Vec<[usize; 2]>,
+    matches: Vec<Vec<Match>>,
+    duplicates: Vec<DuplicateKey>,
+    error: Option<ParseError>,
+    // The first duplicate key, with `DuplicateKeys::Error`, tracked apart
+    // from `error` so that an earlier error cannot hide it
+    duplicate: Option<ParseError>,
+}
+
+// Parse `json` and find its ranges and duplicate keys, along with the first
+// error in it, and what each rule matched when `with_matches` is set
+fn analyze(json: &str, policy: &Policy, options: &Options, with_matches: bool) -> Analysis {
+    let document = parse(json, options.strict);
+    let error = document.error;
+    let mut duplicate = None;
+    let mut ranges = Vec::new();
+    let mut duplicates = Vec::new();
+    let mut matches = vec![Vec::new(); policy.rules.len()];
+
+    let mut collector = Collector {
+        json,
+        policy,
+        keys: options.keys,
+        whitespace: options.whitespace,
+        matcher: Matcher::new(policy.rules.iter().map(|rule| rule.path.clone()).collect()),
+        shadowed: Vec::new(),
+    };
+    match &document.root {
+        Some(root) => {
+            duplicates = find_duplicates(root, options.duplicates);
+            if options.duplicates == DuplicateKeys::Error {
+                if let Some(position) = duplicates
+                    .iter()
+                    .map(|duplicate| duplicate.keys[1][0])
+                    .min()
+                {
+                    duplicate = Some(ParseError::new(
+                        json,
+                        position,
+                        ParseErrorKind::DuplicateKey,
+                    ));
+                }
+            }
+            // The keys of the occurrences the rules do not apply to
+            collector.shadowed = duplicates
+                .iter()
+                .flat_map(|duplicate| {
+                    let kept = duplicate.kept;
+                    duplicate
+                        .keys
+                        .iter()
+                        .enumerate()
+                        .filter(move |&(i, _)| kept.is_some_and(|kept| kept != i))
+                        .map(|(_, key)| key[0])
+                })
+                .collect();
+            collector.shadowed.sort_unstable();
+            if with_matches {
+                matches = find_matches(
+                    root,
+                    json,
+                    &collector.matcher,
+                    &collector.shadowed,
+                    policy.rules.len(),
+                );
+            }
+
+            // Whitespace around the root value
+            collector.push_gap([0, root.start], false, &mut ranges);
+            let states = collector.matcher.root();
+            collector.collect(root, &states, (policy.default, None), &mut ranges);
+            collector.push_gap([root.end, document.end], false, &mut ranges);
+        }
+        None => collector.push_gap([0, document.end], false, &mut ranges),
+    }
+
+    let mut ranges = merge_ranges(ranges);
+    // Nothing past the first error is disclosed, even where parsing went on
+    if let Some(error) = &error {
+        ranges.retain(|range| range[0] < error.position);
+        if let Some(last) = ranges.last_mut() {
+            last[1] = last[1].min(error.position);
+        }
+    }
+    let mut redacted = Vec::new();
+    let mut cursor = 0;
+    for &[start, end] in ranges.iter().chain([&[json.len(), json.len()]]) {
+        if cursor < start {
+            redacted.push([cursor, start]);
+        }
+        cursor = end;
+    }
+
+    let convert =
+        |ranges: &[[usize; 2]]| convert_ranges(json, ranges, OffsetUnit::Utf8, options.unit);
+    for duplicate in &mut duplicates {
+        duplicate.keys = convert(&duplicate.keys);
+    }
+    for found in matches.iter_mut().flatten() {
+        let span = convert(&[found.span]);
+        found.span = span.first().copied().unwrap_or_default();
+    }
+    Analysis {
+        ranges: convert(&ranges),
+        redacted: convert(&redacted),
+        matches,
+        duplicates,
+        error,
+        duplicate,
+    }
+}
+
+struct Collector<'a> {
+    json: &'a str,
+    policy: &'a Policy,
+    keys: KeyMode,
+    whitespace: WhitespaceMode,
+    matcher: Matcher,
+    // Sorted starts of the keys of duplicated members the rules do not apply
+    // to, which are always redacted
+    shadowed: Vec<usize>,
+}
+
+impl Collector<'_> {
+    // Push the ranges to keep within `node`, which the traversal reached in
+    // `states` and which inherits `disclosure` and the part of scalars to
+    // reveal from its closest enclosing value matched by a rule. Scalars are
+    // kept when revealed, and the structure between the children of
+    // containers is kept depending on the key and whitespace modes. Returns
+    // whether anything in `node` was revealed.
+    fn collect(
+        &self,
+        node: &Node,
+        states: &States,
+        inherited: (Disclosure, Option<&Part>),
+        ranges: &mut Vec<[usize; 2]>,
+    ) -> bool {
+        let (disclosure, part) = self
+            .matcher
+            .matched(states)
+            .map(|rule| &self.policy.rules[rule])
+            .max_by_key(|rule| (rule.disclosure, rule.part.is_some()))
+            .map_or(inherited, |rule| (rule.disclosure, rule.part.as_ref()));
+        let disclosed = disclosure == Disclosure::Reveal && part.is_none();
+        // No rule can match below this node, and nothing in it must be redacted
+        let shadows = self.shadowed.partition_point(|&key| key < node.start)
+            < self.shadowed.partition_point(|&key| key < node.end);
+        if states.is_empty() && disclosed && self.whitespace != WhitespaceMode::Redact && !shadows {
+            ranges.push([node.start, node.end]);
+            return true;
+        }
+
+        let mut revealed = disclosure == Disclosure::Reveal;
+        match &node.kind {
+            NodeKind::Object(members) => {
+                let mut cursor = node.start;
+                let mut previous_shown = true;
+                for member in members {
+                    let mut value_ranges = Vec::new();
+                    let shown = if self.shadowed.binary_search(&member.key.start).is_ok() {
+                        self.collect(
+                            &member.value,
+                            &States::new(),
+                            (Disclosure::Redact, None),
+                            &mut value_ranges,
+                        )
+                    } else {
+                        self.collect(
+                            &member.value,
+                            &self.matcher.step(states, Step::Member(&member.name)),
+                            (disclosure, part),
+                            &mut value_ranges,
+                        )
+                    };
+                    revealed |= shown;
+
+                    let separator = [cursor, member.key.start];
+                    let key = [member.key.start, member.key.end];
+                    let colon = [member.key.end, member.value.start];
+                    match self.keys {
+                        KeyMode::Reveal => {
+                            self.push_gap(separator, disclosed, ranges);
+                            ranges.push(key);
+                            self.push_gap(colon, disclosed, ranges);
+                        }
+                        KeyMode::Redact => {
+                            self.push_gap(separator, disclosed, ranges);
+                            self.push_gap(colon, disclosed, ranges);
+                            if shown {
+                                ranges.push(key);
+                            }
+                        }
+                        KeyMode::HideEntries => {
+                            // The separator before the first member only
+                            // holds the opening brace and whitespace
+                            if cursor == node.start || (shown && previous_shown) {
+                                self.push_gap(separator, disclosed, ranges);
+                            }
+                            if shown {
+                                ranges.push(key);
+                                self.push_gap(colon, disclosed, ranges);
+                            } else {
+                                value_ranges.clear();
+                            }
+                        }
+                    }
+                    ranges.append(&mut value_ranges);
+                    previous_shown = shown;
+                    cursor = member.value.end;
+                }
+                self.push_gap([cursor, node.end], disclosed, ranges);
+            }
+            NodeKind::Array(items) => {
+                let mut cursor = node.start;
+                for (index, item) in items.iter().enumerate() {
+                    self.push_gap([cursor, item.start], disclosed, ranges);
+                    let step = Step::Element {
+                        index,
+                        len: items.len(),
+                        item,
+                        json: self.json,
+                        shadowed: &self.shadowed,
+                    };
+                    revealed |= self.collect(
+                        item,
+                        &self.matcher.step(states, step),
+                        (disclosure, part),
+                        ranges,
+                    );
+                    cursor = item.end;
+                }
+                self.push_gap([cursor, node.end], disclosed, ranges);
+            }
+            NodeKind::String if disclosure == Disclosure::RedactContent => {
+                self.push_quotes(node, ranges);
+                revealed = true;
+            }
+            _ if !revealed => {}
+            // Revealed scalars are kept whole, or only in part
+            _ => match part {
+                Some(part) => self.push_part(node, part, ranges),
+                None => ranges.push([node.start, node.end]),
+            },
+        }
+        revealed
+    }
+
+    // Push `gap`, which holds the structural characters and whitespace between
+    // two tokens inside a container, leaving out the whitespace unless the
+    // whitespace mode keeps it. `disclosed` tells whether the container is
+    // revealed in full.
+    fn push_gap(&self, [start, end]: [usize; 2], disclosed: bool, ranges: &mut Vec<[usize; 2]>) {
+        let keep = match self.whitespace {
+            WhitespaceMode::Reveal => true,
+            WhitespaceMode::Redact => false,
+            WhitespaceMode::Disclosed => disclosed,
+        };
+        if keep {
+            ranges.push([start, end]);
+            return;
+        }
+        let bytes = self.json.as_bytes();
+        let mut cursor = start;
+        while cursor < end {
+            let piece = cursor;
+            while cursor < end && !is_whitespace(bytes[cursor]) {
+                cursor += 1;
+            }
+            ranges.push([piece, cursor]);
+            while cursor < end && is_whitespace(bytes[cursor]) {
+                cursor += 1;
+            }
+        }
+    }
+
+    // Push the quotes of the string `node` and return the offset and text of
+    // its contents
+    fn push_quotes(&self, node: &Node, ranges: &mut Vec<[usize; 2]>) -> (usize, &str) {
+        let raw = &self.json[node.start..node.end];
+        if raw.len() >= 2 && raw.ends_with('"') {
+            ranges.extend([[node.start, node.start + 1], [node.end - 1, node.end]]);
+            (node.start + 1, &raw[1..raw.len() - 1])
+        } else {
+            // An unterminated string only has its opening quote
+            ranges.push([node.start, node.start + 1]);
+            (node.start + 1, &raw[1..])
+        }
+    }
+
+    // Push the range of `part` of the scalar `node`, along with the quotes of
+    // a string
+    fn push_part(&self, node: &Node, part: &Part, ranges: &mut Vec<[usize; 2]>) {
+        let (offset, chars) = match node.kind {
+            NodeKind::String => {
+                let (offset, content) = self.push_quotes(node, ranges);
+                (offset, decode_chars(content))
+            }
+            _ => (
+                node.start,
+                self.json[node.start..node.end]
+                    .char_indices()
+                    .map(|(i, c)| (c, i, i + c.len_utf8()))
+                    .collect(),
+            ),
+        };
+        if let Some([start, end]) = part.select(&chars) {
+            ranges.push([offset + chars[start].1, offset + chars[end - 1].2]);
+        }
+    }
+}
+
+// Sort the ranges, drop empty ones and merge those that touch or overlap
+pub(crate) fn merge_ranges(mut ranges: Vec<[usize; 2]>) -> Vec<[usize; 2]> {
+    ranges.retain(|range| range[0] < range[1]);
+    ranges.sort_by_key(|range| range[0]);
+
+    let mut merged_ranges: Vec<[usize; 2]> = Vec::new();
+    for range in ranges {
+        match merged_ranges.last_mut() {
+            Some(last_range) if last_range[1] >= range[0] => {
+                last_range[1] = range[1].max(last_range[1]);
+            }
+            _ => merged_ranges.push(range),
+        }
+    }
+    merged_ranges
+}
@@ -0,0 +1,283 @@
+use crate::parser::{parse, Node, NodeKind};
+
+/// Render `json` with every part outside `ranges` replaced by `"<REDACTED>"`.
+/// `ranges` are sorted byte offsets as returned by [`find_ranges`](crate::find_ranges).
+pub fn redact_json(json: &str, ranges: Vec<[usize; 2]>) -> String {
+    let mut result = String::new();
+    let mut last_idx = 0;
+
+    for range in ranges {
+        // Append the redacted portion if there's a gap between ranges
+        if last_idx < range[0] {
+            result.push_str("\"<REDACTED>\"");
+        }
+
+        // Append the preserved portion from the range
+        result.push_str(&json[range[0]..range[1]]);
+        last_idx = range[1];
+    }
+
+    // Handle any remaining content after the last range
+    if last_idx < json.len() {
+        result.push_str("\"<REDACTED>\"");
+    }
+
+    result
+}
+
+/// Render `json` with every byte outside `ranges` replaced by `filler`, so the
+/// result is byte-for-byte aligned with `json`, like the redacted transcript a
+/// TLSNotary verifier sees.
+///
+/// Ranges may be in any order and may overlap. A range boundary inside a
+/// multi-byte character is moved inwards, redacting that character.
+///
+/// # Panics
+///
+/// Panics if `filler` is not an ASCII byte, which could not keep the result
+/// valid UTF-8.
+pub fn redact_json_filled(json: &str, ranges: &[[usize; 2]], filler: u8) -> String {
+    assert!(filler.is_ascii(), "filler must be an ASCII byte");
+
+    let mut result = vec![filler; json.len()];
+    for &[start, end] in ranges {
+        let mut start = start.min(json.len());
+        let mut end = end.min(json.len());
+        while !json.is_char_boundary(start) {
+            start += 1;
+        }
+        while !json.is_char_boundary(end) {
+            end -= 1;
+        }
+        if start < end {
+            result[start..end].copy_from_slice(&json.as_bytes()[start..end]);
+        }
+    }
+
+    String::from_utf8(result).expect("kept ranges lie on char boundaries")
+}
+
+/// The placeholders [`redact_json_with`] puts in place of hidden values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PlaceholderStyle {
+    /// `"<REDACTED>"` for every hidden value or key, as in [`redact_json`],
+    /// with hidden whitespace and entries dropped
+    Marker,
+    /// A placeholder of the same type and length as the hidden value: a
+    /// string of `filler` bytes inside the original quotes, `0` for a number
+    /// and `null` for `true` or `false`, padded with spaces as needed. Hidden
+    /// whitespace and entries become spaces, so the output stays aligned with
+    /// the input.
+    Typed { filler: u8 },
+}
+
+/// Render `json` with the parts outside `ranges` replaced according to
+/// `style`, keeping the result valid JSON whenever `json` is: commas dropped
+/// along with hidden entries are put back between the remaining ones, and
+/// hidden brackets, braces and colons are restored.
+///
+/// ```
+/// use json_str_redactor::{find_ranges, redact_json_with, PlaceholderStyle};
+///
+/// let json = r#"{"name":"Alice","age":30,"ok":true}"#;
+/// let ranges = find_ranges(json, &[vec!["name"]]);
+/// assert_eq!(
+///     redact_json_with(json, &ranges, PlaceholderStyle::Typed { filler: b'X' }),
+///     r#"{"name":"Alice","age":0 ,"ok":null}"#
+/// );
+/// ```
+///
+/// # Panics
+///
+/// Panics if a `Typed` filler is not an ASCII character other than `"` or
+/// `\`, which could not be put inside a string.
+pub fn redact_json_with(json: &str, ranges: &[[usize; 2]], style: PlaceholderStyle) -> String {
+    if let PlaceholderStyle::Typed { filler } = style {
+        assert!(
+            filler.is_ascii() && filler != b'"' && filler != b'\\',
+            "filler must be an ASCII character other than a quote or backslash"
+        );
+    }
+
+    let mut kept = vec![false; json.len()];
+    for &[start, end] in ranges {
+        for kept in &mut kept[start.min(json.len())..end.min(json.len())] {
+            *kept = true;
+        }
+    }
+
+    let mut renderer = Renderer {
+        json,
+        kept,
+        style,
+        replacements: Vec::new(),
+    };
+    if let Some(root) = parse(json, false).root {
+        renderer.render(&root);
+    }
+    renderer.finish()
+}
+
+struct Renderer<'a> {
+    json: &'a str,
+    kept: Vec<bool>,
+    style: PlaceholderStyle,
+    // Non-overlapping `(start, end, text)` replacements for hidden parts
+    replacements: Vec<(usize, usize, String)>,
+}
+
+impl Renderer<'_> {
+    fn is_hidden(&self, start: usize, end: usize) -> bool {
+        self.kept[start..end].iter().any(|&kept| !kept)
+    }
+
+    // Put back a structural character if it was hidden
+    fn restore(&mut self, at: usize) {
+        if !self.kept[at] {
+            let c = self.json[at..at + 1].to_string();
+            self.replacements.push((at, at + 1, c));
+        }
+    }
+
+    fn render(&mut self, node: &Node) {
+        match &node.kind {
+            NodeKind::Object(members) => {
+                self.restore(node.start);
+                let mut previous_end = None;
+                for member in members {
+                    let colon = self.json[member.key.end..member.value.start]
+                        .find(':')
+                        .map(|offset| member.key.end + offset);
+                    // A member whose key and colon are hidden is left out
+                    let Some(colon) = colon.filter(|&colon| {
+                        !self.is_hidden(member.key.start, member.key.end) || self.kept[colon]
+                    }) else {
+                        continue;
+                    };
+                    if let Some(previous_end) = previous_end {
+                        self.separate(previous_end, member.key.start);
+                    }
+                    self.render_string(member.key.start, member.key.end);
+                    self.restore(colon);
+                    self.render(&member.value);
+                    previous_end = Some(member.value.end);
+                }
+                self.close(node);
+            }
+            NodeKind::Array(items) => {
+                self.restore(node.start);
+                for pair in items.windows(2) {
+                    self.separate(pair[0].end, pair[1].start);
+                }
+                for item in items {
+                    self.render(item);
+                }
+                self.close(node);
+            }
+            NodeKind::String => self.render_string(node.start, node.end),
+            NodeKind::Number | NodeKind::Literal if self.is_hidden(node.start, node.end) => {
+                let len = node.end - node.start;
+                let text = match self.style {
+                    PlaceholderStyle::Marker => "\"<REDACTED>\"".to_string(),
+                    PlaceholderStyle::Typed { .. } => {
+                        let placeholder = match node.kind {
+                            NodeKind::Literal if len >= 4 => "null",
+                            _ => "0",
+                        };
+                        format!("{placeholder:len$}")
+                    }
+                };
+                self.replacements.push((node.start, node.end, text));
+            }
+            NodeKind::Number | NodeKind::Literal => {}
+        }
+    }
+
+    fn close(&mut self, node: &Node) {
+        let last = node.end - 1;
+        if last > node.start && matches!(self.json.as_bytes()[last], b'}' | b']') {
+            self.restore(last);
+        }
+    }
+
+    // Make sure the text between two siblings holds a comma
+    fn separate(&mut self, start: usize, end: usize) {
+        let gap = &self.json.as_bytes()[start..end];
+        let has_kept_comma = (start..end).any(|i| gap[i - start] == b',' && self.kept[i]);
+        if !has_kept_comma {
+            if let Some(at) = (start..end).find(|&i| !self.kept[i]) {
+                self.replacements.push((at, at + 1, ",".to_string()));
+            }
+        }
+    }
+
+    // Replace the hidden parts of a string token, keeping its quotes
+    fn render_string(&mut self, start: usize, end: usize) {
+        if !self.is_hidden(start, end) {
+            return;
+        }
+        let bytes = self.json.as_bytes();
+        let mut text = String::from("\"");
+        let mut i = start + 1;
+        while i < end - 1 {
+            if self.kept[i] {
+                let len = utf8_len(bytes[i]);
+                text.push_str(&self.json[i..i + len]);
+                i += len;
+                continue;
+            }
+            // A kept backslash right before a hidden byte would escape the
+            // placeholder, so make it an escaped backslash instead
+            let escaping = text.bytes().rev().take_while(|&b| b == b'\\').count() % 2 == 1;
+            match self.style {
+                PlaceholderStyle::Typed { .. } if escaping => text.push('\\'),
+                PlaceholderStyle::Typed { filler } => text.push(filler as char),
+                PlaceholderStyle::Marker => {
+                    if escaping {
+                        text.push('\\');
+                    }
+                    text.push_str("<REDACTED>");
+                    while i < end - 1 && !self.kept[i] {
+                        i += 1;
+                    }
+                    continue;
+                }
+            }
+            i += 1;
+        }
+        text.push('"');
+        self.replacements.push((start, end, text));
+    }
+
+    fn finish(mut self) -> String {
+        self.replacements.sort_by_key(|&(start, _, _)| start);
+        let mut result = String::with_capacity(self.json.len());
+        let mut replacements = self.replacements.into_iter().peekable();
+        let mut i = 0;
+        while i < self.json.len() {
+            if let Some((_, end, text)) = replacements.next_if(|&(start, _, _)| start == i) {
+                result.push_str(&text);
+                i = end;
+                continue;
+            }
+            let len = utf8_len(self.json.as_bytes()[i]);
+            if self.kept[i] {
+                result.push_str(&self.json[i..i + len]);
+            } else if let PlaceholderStyle::Typed { .. } = self.style {
+                result.extend(std::iter::repeat_n(' ', len));
+            }
+            i += len;
+        }
+        result
+    }
+}
+
+// The length of the UTF-8 sequence starting with `first`
+fn utf8_len(first: u8) -> usize {
+    match first {
+        0xF0.. => 4,
+        0xE0.. => 3,
+        0xC0.. => 2,
+        _ => 1,
+    }
+}
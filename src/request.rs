@@ -0,0 +1,219 @@
+use crate::duplicates::DuplicateKeys;
+use crate::http::{
+    body_ranges, is_token_char, message_ranges, parse_message, try_body_ranges, HeaderPolicy,
+    HeaderRule, HttpError, HttpErrorKind, Message,
+};
+use crate::policy::{Disclosure, Policy};
+use crate::ranges::Options;
+
+/// Which parts of an HTTP/1.1 request transcript to disclose.
+///
+/// The method, the HTTP version, the slashes of the path and the names and
+/// separators of the query are always kept, along with the header names and
+/// framing. Path segments and query values are redacted unless revealed,
+/// header values are disclosed according to `headers`, which redacts them all
+/// by default and keeps `Authorization`, `Proxy-Authorization` and `Cookie`
+/// hidden even when others are revealed by default, unless a rule names them,
+/// and the JSON body according to `body`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RequestPolicy {
+    /// The indices of the revealed path segments, counted from 0, so that the
+    /// segments of `/users/42/posts` are `users`, `42` and `posts`
+    pub path: Vec<usize>,
+    /// The names of the query parameters whose values are revealed, compared
+    /// as written in the transcript. A parameter without `=` is revealed
+    /// whole or not at all.
+    pub query: Vec<String>,
+    /// How header values are disclosed
+    pub headers: HeaderPolicy,
+    /// How the JSON body is disclosed
+    pub body: Policy,
+}
+
+impl RequestPolicy {
+    /// Disclose the body according to `body`, redacting the path, query and
+    /// every header value.
+    pub fn new(body: Policy) -> Self {
+        RequestPolicy {
+            path: Vec::new(),
+            query: Vec::new(),
+            headers: HeaderPolicy {
+                default: Disclosure::Redact,
+                rules: Vec::new(),
+                sensitive: ["Authorization", "Proxy-Authorization", "Cookie"]
+                    .map(String::from)
+                    .to_vec(),
+            },
+            body,
+        }
+    }
+
+    /// Reveal the path segments at `indices`.
+    pub fn reveal_path_segments(mut self, indices: &[usize]) -> Self {
+        self.path.extend(indices);
+        self
+    }
+
+    /// Reveal the values of the query parameters named `names`.
+    pub fn reveal_query(mut self, names: &[&str]) -> Self {
+        self.query.extend(names.iter().map(|name| name.to_string()));
+        self
+    }
+
+    /// Reveal the values of the headers named `names`.
+    pub fn reveal_headers(self, names: &[&str]) -> Self {
+        names
+            .iter()
+            .fold(self, |policy, name| policy.header(HeaderRule::reveal(name)))
+    }
+
+    /// Redact the values of the headers named `names`.
+    pub fn redact_headers(self, names: &[&str]) -> Self {
+        names
+            .iter()
+            .fold(self, |policy, name| policy.header(HeaderRule::redact(name)))
+    }
+
+    /// Add a header rule.
+    pub fn header(mut self, rule: HeaderRule) -> Self {
+        self.headers.rules.push(rule);
+        self
+    }
+}
+
+/// Find the ranges of an HTTP/1.1 request `transcript` to keep so that it is
+/// disclosed according to `policy`, like
+/// [`find_response_ranges`](crate::find_response_ranges) does for a response.
+/// The request target must be a path, optionally followed by a query, or `*`.
+/// A request without `Content-Length` or `Transfer-Encoding` has no body.
+///
+/// ```
+/// use json_str_redactor::{find_request_ranges, Policy, RequestPolicy};
+///
+/// let transcript = b"GET /users/42?fields=name&token=s3cret HTTP/1.1\r\nAuthorization: Bearer x\r\n\r\n";
+/// let policy = RequestPolicy::new(Policy::allow(&[vec!["id"]]))
+///     .reveal_path_segments(&[0])
+///     .reveal_query(&["fields"]);
+/// let ranges = find_request_ranges(transcript, &policy, &Default::default());
+/// assert_eq!(ranges, [[0, 11], [13, 32], [38, 64], [72, 76]]);
+/// ```
+pub fn find_request_ranges(
+    transcript: &[u8],
+    policy: &RequestPolicy,
+    options: &Options,
+) -> Vec<[usize; 2]> {
+    if options.strict || options.duplicates == DuplicateKeys::Error {
+        return try_find_request_ranges(transcript, policy, options).unwrap_or_default();
+    }
+    let Ok(request) = parse_request(transcript) else {
+        return Vec::new();
+    };
+    let ranges = body_ranges(transcript, &request.body, &policy.body, options);
+    let hidden = target_ranges(transcript, &request, policy);
+    message_ranges(transcript, &request, &policy.headers, hidden, ranges)
+}
+
+/// Like [`find_request_ranges`], but fails on a malformed transcript or JSON
+/// body with the first error in it.
+pub fn try_find_request_ranges(
+    transcript: &[u8],
+    policy: &RequestPolicy,
+    options: &Options,
+) -> Result<Vec<[usize; 2]>, HttpError> {
+    let request = parse_request(transcript)?;
+    let ranges = try_body_ranges(transcript, &request.body, &policy.body, options)?;
+    let hidden = target_ranges(transcript, &request, policy);
+    Ok(message_ranges(
+        transcript,
+        &request,
+        &policy.headers,
+        hidden,
+        ranges,
+    ))
+}
+
+// Parse the head of an HTTP/1.x request, checking its request line is
+// `token SP request-target SP HTTP/1.x`
+fn parse_request(transcript: &[u8]) -> Result<Message, HttpError> {
+    let message = parse_message(transcript, true)?;
+    let [start, end] = message.start_line;
+    let line = &transcript[start..end];
+    let invalid = HttpError {
+        position: start,
+        kind: HttpErrorKind::InvalidStartLine,
+    };
+    let parts: Vec<&[u8]> = line.split(|&b| b == b' ').collect();
+    let &[method, target, version] = parts.as_slice() else {
+        return Err(invalid);
+    };
+    let valid = !method.is_empty()
+        && method.iter().all(|&b| is_token_char(b))
+        && (target == b"*" || target.starts_with(b"/"))
+        && target.iter().all(|&b| b > b' ' && b != 0x7f)
+        && version.len() == 8
+        && version.starts_with(b"HTTP/1.")
+        && version[7].is_ascii_digit();
+    if valid {
+        Ok(message)
+    } else {
+        Err(invalid)
+    }
+}
+
+// The ranges of the request target of `request` to redact: the path segments
+// and query values `policy` does not reveal, and any fragment
+fn target_ranges(transcript: &[u8], request: &Message, policy: &RequestPolicy) -> Vec<[usize; 2]> {
+    let line = request.start_line;
+    let start = line[0]
+        + transcript[line[0]..line[1]]
+            .iter()
+            .position(|&b| b == b' ')
+            .map_or(0, |space| space + 1);
+    let end = start
+        + transcript[start..line[1]]
+            .iter()
+            .position(|&b| b == b' ')
+            .unwrap_or_default();
+    let target = &transcript[start..end];
+    let fragment = target.iter().position(|&b| b == b'#');
+    let query = target[..fragment.unwrap_or(target.len())]
+        .iter()
+        .position(|&b| b == b'?');
+    let path_end = query.or(fragment).unwrap_or(target.len());
+
+    let mut hidden = Vec::new();
+    // Path segments follow each slash
+    let mut segment_start = 1;
+    for (index, segment) in target[1.min(path_end)..path_end]
+        .split(|&b| b == b'/')
+        .enumerate()
+    {
+        if !policy.path.contains(&index) {
+            hidden.push([start + segment_start, start + segment_start + segment.len()]);
+        }
+        segment_start += segment.len() + 1;
+    }
+    if let Some(query) = query {
+        let query_end = fragment.unwrap_or(target.len());
+        let mut param_start = query + 1;
+        for param in target[query + 1..query_end].split(|&b| b == b'&') {
+            let (name, value_start) = match param.iter().position(|&b| b == b'=') {
+                Some(equals) => (&param[..equals], param_start + equals + 1),
+                None => (param, param_start),
+            };
+            if !policy
+                .query
+                .iter()
+                .any(|revealed| revealed.as_bytes() == name)
+            {
+                hidden.push([start + value_start, start + param_start + param.len()]);
+            }
+            param_start += param.len() + 1;
+        }
+    }
+    if let Some(fragment) = fragment {
+        hidden.push([start + fragment + 1, end]);
+    }
+    hidden.retain(|range| range[0] < range[1]);
+    hidden
+}
@@ -0,0 +1,221 @@
+/// The kind of a lexical token in a JSON string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    BeginObject,
+    EndObject,
+    BeginArray,
+    EndArray,
+    Colon,
+    Comma,
+    /// A string including both quotes, or running to the end of the input if
+    /// the closing quote is missing
+    String,
+    /// A bare word starting with `-` or a digit
+    Number,
+    /// Any other bare word, e.g. `true`, `false` or `null`
+    Literal,
+    Whitespace,
+}
+
+/// A token together with its byte offsets `[start, end)` in the scanned string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A lenient tokenizer that splits a JSON string into consecutive tokens
+/// covering every byte of the input.
+pub struct Scanner<'a> {
+    json: &'a str,
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(json: &'a str) -> Self {
+        Scanner { json, pos: 0 }
+    }
+
+    // Index of the first byte at or after `from` that is not part of a bare
+    // word, i.e. whitespace, a quote or a structural character
+    fn bare_word_end(&self, from: usize) -> usize {
+        self.json.as_bytes()[from..]
+            .iter()
+            .position(|&b| is_whitespace(b) || b == b'"' || is_structural(b))
+            .map_or(self.json.len(), |len| from + len)
+    }
+
+    // Index just past the closing quote of the string starting at `from`,
+    // skipping over escaped characters
+    fn string_end(&self, from: usize) -> usize {
+        let bytes = self.json.as_bytes();
+        let mut i = from + 1;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\\' => i += 2,
+                b'"' => return i + 1,
+                _ => i += 1,
+            }
+        }
+        bytes.len()
+    }
+}
+
+impl Iterator for Scanner<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        let bytes = self.json.as_bytes();
+        let start = self.pos;
+        let first = *bytes.get(start)?;
+
+        let (kind, end) = match first {
+            b'{' => (TokenKind::BeginObject, start + 1),
+            b'}' => (TokenKind::EndObject, start + 1),
+            b'[' => (TokenKind::BeginArray, start + 1),
+            b']' => (TokenKind::EndArray, start + 1),
+            b':' => (TokenKind::Colon, start + 1),
+            b',' => (TokenKind::Comma, start + 1),
+            b'"' => (TokenKind::String, self.string_end(start)),
+            b if is_whitespace(b) => {
+                let len = bytes[start..]
+                    .iter()
+                    .take_while(|&&b| is_whitespace(b))
+                    .count();
+                (TokenKind::Whitespace, start + len)
+            }
+            b'-' | b'0'..=b'9' => (TokenKind::Number, self.bare_word_end(start)),
+            _ => (TokenKind::Literal, self.bare_word_end(start)),
+        };
+
+        // An escape right before the end of the input can step past it
+        let end = end.min(bytes.len());
+        self.pos = end;
+        Some(Token { kind, start, end })
+    }
+}
+
+pub(crate) fn is_whitespace(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+fn is_structural(b: u8) -> bool {
+    matches!(b, b'{' | b'}' | b'[' | b']' | b':' | b',')
+}
+
+// Decode the contents of a string token, without its quotes. Invalid escapes
+// are kept as written and unpaired surrogates become U+FFFD.
+pub(crate) fn decode_string(raw: &str) -> String {
+    decode_chars(raw).into_iter().map(|(c, _, _)| c).collect()
+}
+
+// Decode the contents of a string token like `decode_string`, pairing each
+// decoded char with the `[start, end)` byte span of `raw` it was read from
+pub(crate) fn decode_chars(raw: &str) -> Vec<(char, usize, usize)> {
+    let mut decoded = Vec::with_capacity(raw.len());
+    let mut chars = raw.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c != '\\' {
+            decoded.push((c, start, start + c.len_utf8()));
+            continue;
+        }
+        let escaped = match chars.peek().map(|&(_, c)| c) {
+            Some('"') => '"',
+            Some('\\') => '\\',
+            Some('/') => '/',
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('u') => {
+                if let Some((c, len)) = decode_unicode_escape(&raw[start + 2..]) {
+                    let end = start + 2 + len;
+                    decoded.push((c, start, end));
+                    while chars.next_if(|&(i, _)| i < end).is_some() {}
+                } else {
+                    decoded.push(('\\', start, start + 1));
+                }
+                continue;
+            }
+            // The backslash of an invalid escape is kept as a char of its own
+            _ => {
+                decoded.push(('\\', start, start + 1));
+                continue;
+            }
+        };
+        chars.next();
+        decoded.push((escaped, start, start + 2));
+    }
+    decoded
+}
+
+// Decode the hex digits following a `\u`, combining a surrogate pair written
+// as two consecutive escapes. Returns the char and the number of bytes used.
+fn decode_unicode_escape(s: &str) -> Option<(char, usize)> {
+    let high = hex4(s)?;
+    if !(0xD800..0xE000).contains(&high) {
+        return Some((char::from_u32(high)?, 4));
+    }
+    let low = s[4..].strip_prefix("\\u").and_then(hex4);
+    match low {
+        Some(low) if (0xD800..0xDC00).contains(&high) && (0xDC00..0xE000).contains(&low) => {
+            let c = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+            Some((char::from_u32(c)?, 10))
+        }
+        _ => Some((char::REPLACEMENT_CHARACTER, 4)),
+    }
+}
+
+// The length of the escape sequence at the start of `s`, which starts with a
+// backslash, if it is valid JSON. Surrogates must come in pairs.
+pub(crate) fn escape_len(s: &str) -> Option<usize> {
+    match s.as_bytes().get(1)? {
+        b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't' => Some(2),
+        b'u' => match hex4(&s[2..])? {
+            0xD800..=0xDBFF => {
+                let low = s[6..].strip_prefix("\\u").and_then(hex4)?;
+                (0xDC00..=0xDFFF).contains(&low).then_some(12)
+            }
+            0xDC00..=0xDFFF => None,
+            _ => Some(6),
+        },
+        _ => None,
+    }
+}
+
+fn hex4(s: &str) -> Option<u32> {
+    let digits = s.get(..4)?;
+    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(digits, 16).ok()
+}
+
+// Whether `s` follows the JSON number grammar
+pub(crate) fn is_json_number(s: &str) -> bool {
+    let s = s.strip_prefix('-').unwrap_or(s);
+    let (int, rest) = s.split_at(s.find(['.', 'e', 'E']).unwrap_or(s.len()));
+    let all_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if !all_digits(int) || (int.len() > 1 && int.starts_with('0')) {
+        return false;
+    }
+    let (fraction, exponent) = match rest.find(['e', 'E']) {
+        Some(e) => (&rest[..e], Some(&rest[e + 1..])),
+        None => (rest, None),
+    };
+    (fraction.is_empty() || fraction.strip_prefix('.').is_some_and(all_digits))
+        && exponent.is_none_or(|exponent| {
+            all_digits(exponent.strip_prefix(['+', '-']).unwrap_or(exponent))
+        })
+}
+
+// Whether the raw string token `raw` ends with its closing quote rather than
+// running to the end of the input
+pub(crate) fn is_terminated_string(raw: &str) -> bool {
+    let Some(content) = raw.get(1..).and_then(|raw| raw.strip_suffix('"')) else {
+        return false;
+    };
+    content.bytes().rev().take_while(|&b| b == b'\\').count() % 2 == 0
+}
@@ -0,0 +1,447 @@
+use crate::filter::{Comparison, Filter, Literal};
+use crate::path::Segment;
+use crate::scanner::is_json_number;
+use std::fmt;
+use std::str::FromStr;
+
+/// A key sequence written as a JSONPath-style selector.
+///
+/// Supported syntax:
+/// - `$` for the root, which every selector starts with
+/// - `.name` or `['name']` / `["name"]` for an object member
+/// - `[0]`, `[-1]` for an array element, counting from the end when negative
+/// - `[1:3]`, `[:2]`, `[-2:]` for a range of array elements
+/// - `.*` or `[*]` for any array element or object member
+/// - `..name`, `..*` or `..[0]` to apply the following segment at any depth
+/// - `[?(@.type == 'email')]` for the array elements passing a [`Filter`],
+///   comparing a value below the element (`@`, `@.a.b`, `@['a'][0]`) with a
+///   string, number, `true`, `false` or `null` using `==`, `!=`, `<`, `<=`,
+///   `>` or `>=`, or just testing that it exists as in `[?(@.email)]`
+///
+/// Like key sequences, arrays are transparent to member names, so `$.a.b`
+/// also matches `b` in every element of an array `a`.
+///
+/// ```
+/// use json_str_redactor::{Segment, Selector};
+///
+/// let selector: Selector = "$.contacts[0]['type']".parse().unwrap();
+/// assert_eq!(
+///     selector.segments(),
+///     [Segment::from("contacts"), Segment::Index(0), Segment::from("type")]
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Selector {
+    segments: Vec<Segment>,
+}
+
+impl Selector {
+    pub fn parse(selector: &str) -> Result<Self, SelectorError> {
+        SelectorParser { selector, pos: 0 }.parse()
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+}
+
+impl FromStr for Selector {
+    type Err = SelectorError;
+
+    fn from_str(selector: &str) -> Result<Self, SelectorError> {
+        Selector::parse(selector)
+    }
+}
+
+impl From<Selector> for Vec<Segment> {
+    fn from(selector: Selector) -> Self {
+        selector.segments
+    }
+}
+
+/// An error parsing a [`Selector`], at byte offset `position` of the selector.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SelectorError {
+    pub position: usize,
+    pub kind: SelectorErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SelectorErrorKind {
+    /// The selector does not start with `$`
+    MissingRoot,
+    /// The selector ended in the middle of a segment
+    UnexpectedEnd,
+    /// A character that cannot appear at this point
+    UnexpectedChar(char),
+    /// A `.` not followed by a member name or `*`
+    EmptyName,
+    /// An array index that is not a valid integer
+    InvalidIndex,
+    /// A quoted member name without its closing quote
+    UnterminatedString,
+    /// An unknown escape sequence in a quoted member name
+    InvalidEscape,
+    /// A filter expression that does not start with `@`
+    MissingCurrent,
+    /// A filter comparing against something other than a JSON scalar
+    InvalidLiteral,
+}
+
+impl fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            SelectorErrorKind::MissingRoot => write!(f, "selector must start with `$`")?,
+            SelectorErrorKind::UnexpectedEnd => write!(f, "unexpected end of selector")?,
+            SelectorErrorKind::UnexpectedChar(c) => write!(f, "unexpected character {c:?}")?,
+            SelectorErrorKind::EmptyName => write!(f, "expected a member name or `*`")?,
+            SelectorErrorKind::InvalidIndex => write!(f, "invalid array index")?,
+            SelectorErrorKind::UnterminatedString => write!(f, "unterminated quoted name")?,
+            SelectorErrorKind::InvalidEscape => write!(f, "invalid escape sequence")?,
+            SelectorErrorKind::MissingCurrent => write!(f, "filter must start with `@`")?,
+            SelectorErrorKind::InvalidLiteral => {
+                write!(f, "expected a string, number, `true`, `false` or `null`")?
+            }
+        }
+        write!(f, " at offset {}", self.position)
+    }
+}
+
+impl std::error::Error for SelectorError {}
+
+struct SelectorParser<'a> {
+    selector: &'a str,
+    pos: usize,
+}
+
+impl SelectorParser<'_> {
+    fn error(&self, kind: SelectorErrorKind) -> SelectorError {
+        SelectorError {
+            position: self.pos,
+            kind,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.selector[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), SelectorError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.pos += c.len_utf8();
+                Ok(())
+            }
+            Some(c) => Err(self.error(SelectorErrorKind::UnexpectedChar(c))),
+            None => Err(self.error(SelectorErrorKind::UnexpectedEnd)),
+        }
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn parse(mut self) -> Result<Selector, SelectorError> {
+        self.skip_spaces();
+        if self.peek() != Some('$') {
+            return Err(self.error(SelectorErrorKind::MissingRoot));
+        }
+        self.bump();
+
+        let mut segments = Vec::new();
+        loop {
+            match self.peek() {
+                Some('.') => {
+                    self.bump();
+                    if self.peek() == Some('.') {
+                        self.bump();
+                        segments.push(Segment::Descendants);
+                        if self.peek() == Some('[') {
+                            continue;
+                        }
+                    }
+                    segments.push(self.parse_dot_segment()?);
+                }
+                Some('[') => {
+                    self.bump();
+                    segments.push(self.parse_bracket_segment()?);
+                }
+                Some(c) if c.is_whitespace() => {
+                    self.skip_spaces();
+                    if let Some(c) = self.peek() {
+                        return Err(self.error(SelectorErrorKind::UnexpectedChar(c)));
+                    }
+                }
+                Some(c) => return Err(self.error(SelectorErrorKind::UnexpectedChar(c))),
+                None => return Ok(Selector { segments }),
+            }
+        }
+    }
+
+    // After a `.`: a member name or `*`
+    fn parse_dot_segment(&mut self) -> Result<Segment, SelectorError> {
+        if self.peek() == Some('*') {
+            self.bump();
+            return Ok(Segment::Wildcard);
+        }
+        Ok(Segment::Key(self.parse_name()?))
+    }
+
+    fn parse_name(&mut self) -> Result<String, SelectorError> {
+        let start = self.pos;
+        while self.peek().is_some_and(is_name_char) {
+            self.bump();
+        }
+        if start == self.pos {
+            return Err(self.error(SelectorErrorKind::EmptyName));
+        }
+        Ok(self.selector[start..self.pos].to_string())
+    }
+
+    // After a `[`: a quoted name, `*`, an index or a slice, then `]`
+    fn parse_bracket_segment(&mut self) -> Result<Segment, SelectorError> {
+        self.skip_spaces();
+        let segment = match self.peek() {
+            Some('*') => {
+                self.bump();
+                Segment::Wildcard
+            }
+            Some(quote @ ('\'' | '"')) => {
+                self.bump();
+                Segment::Key(self.parse_quoted(quote)?)
+            }
+            Some('?') => {
+                self.bump();
+                Segment::Filter(self.parse_filter()?)
+            }
+            Some(_) => {
+                let start = self.parse_index()?;
+                self.skip_spaces();
+                if self.peek() == Some(':') {
+                    self.bump();
+                    self.skip_spaces();
+                    Segment::Slice(start, self.parse_index()?)
+                } else {
+                    let position = self.pos;
+                    start.map(Segment::Index).ok_or(SelectorError {
+                        position,
+                        kind: SelectorErrorKind::InvalidIndex,
+                    })?
+                }
+            }
+            None => return Err(self.error(SelectorErrorKind::UnexpectedEnd)),
+        };
+        self.skip_spaces();
+        self.expect(']')?;
+        Ok(segment)
+    }
+
+    // After a `?`: a filter expression, optionally in parentheses
+    fn parse_filter(&mut self) -> Result<Filter, SelectorError> {
+        self.skip_spaces();
+        let parenthesized = self.peek() == Some('(');
+        if parenthesized {
+            self.bump();
+            self.skip_spaces();
+        }
+        if self.peek() != Some('@') {
+            return Err(self.error(SelectorErrorKind::MissingCurrent));
+        }
+        self.bump();
+
+        let mut path = Vec::new();
+        loop {
+            match self.peek() {
+                Some('.') => {
+                    self.bump();
+                    path.push(Segment::Key(self.parse_name()?));
+                }
+                Some('[') => {
+                    self.bump();
+                    self.skip_spaces();
+                    let segment = match self.peek() {
+                        Some(quote @ ('\'' | '"')) => {
+                            self.bump();
+                            Segment::Key(self.parse_quoted(quote)?)
+                        }
+                        _ => {
+                            let position = self.pos;
+                            Segment::Index(self.parse_index()?.ok_or(SelectorError {
+                                position,
+                                kind: SelectorErrorKind::InvalidIndex,
+                            })?)
+                        }
+                    };
+                    self.skip_spaces();
+                    self.expect(']')?;
+                    path.push(segment);
+                }
+                _ => break,
+            }
+        }
+
+        self.skip_spaces();
+        let condition = match self.parse_comparison()? {
+            Some(comparison) => {
+                self.skip_spaces();
+                Some((comparison, self.parse_literal()?))
+            }
+            None => None,
+        };
+        self.skip_spaces();
+        if parenthesized {
+            self.expect(')')?;
+        }
+        Ok(Filter { path, condition })
+    }
+
+    fn parse_comparison(&mut self) -> Result<Option<Comparison>, SelectorError> {
+        let rest = &self.selector[self.pos..];
+        let (comparison, len) = if rest.starts_with("==") {
+            (Comparison::Eq, 2)
+        } else if rest.starts_with("!=") {
+            (Comparison::Ne, 2)
+        } else if rest.starts_with("<=") {
+            (Comparison::Le, 2)
+        } else if rest.starts_with(">=") {
+            (Comparison::Ge, 2)
+        } else if rest.starts_with('<') {
+            (Comparison::Lt, 1)
+        } else if rest.starts_with('>') {
+            (Comparison::Gt, 1)
+        } else if let Some(c @ ('=' | '!')) = self.peek() {
+            // Only valid as part of `==` or `!=`
+            return Err(self.error(SelectorErrorKind::UnexpectedChar(c)));
+        } else {
+            return Ok(None);
+        };
+        self.pos += len;
+        Ok(Some(comparison))
+    }
+
+    fn parse_literal(&mut self) -> Result<Literal, SelectorError> {
+        let start = self.pos;
+        if let Some(quote @ ('\'' | '"')) = self.peek() {
+            self.bump();
+            return Ok(Literal::String(self.parse_quoted(quote)?));
+        }
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.'))
+        {
+            self.bump();
+        }
+        let literal = match &self.selector[start..self.pos] {
+            "true" => Some(Literal::Bool(true)),
+            "false" => Some(Literal::Bool(false)),
+            "null" => Some(Literal::Null),
+            number if is_json_number(number) => number.parse().ok().map(Literal::Number),
+            _ => None,
+        };
+        literal.ok_or(SelectorError {
+            position: start,
+            kind: SelectorErrorKind::InvalidLiteral,
+        })
+    }
+
+    // An optional integer, as found on either side of a slice's `:`
+    fn parse_index(&mut self) -> Result<Option<i64>, SelectorError> {
+        let start = self.pos;
+        if self.peek() == Some('-') {
+            self.bump();
+        }
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+        }
+        let digits = &self.selector[start..self.pos];
+        if digits.is_empty() {
+            return Ok(None);
+        }
+        digits.parse().map(Some).map_err(|_| SelectorError {
+            position: start,
+            kind: SelectorErrorKind::InvalidIndex,
+        })
+    }
+
+    // The rest of a name quoted with `quote`, decoding escapes
+    fn parse_quoted(&mut self, quote: char) -> Result<String, SelectorError> {
+        let mut name = String::new();
+        loop {
+            let position = self.pos;
+            match self.bump() {
+                None => {
+                    return Err(SelectorError {
+                        position,
+                        kind: SelectorErrorKind::UnterminatedString,
+                    })
+                }
+                Some(c) if c == quote => return Ok(name),
+                Some('\\') => {
+                    let escaped = match self.bump() {
+                        Some(c @ ('\'' | '"' | '\\' | '/')) => c,
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => self.parse_unicode_escape(position)?,
+                        _ => {
+                            return Err(SelectorError {
+                                position,
+                                kind: SelectorErrorKind::InvalidEscape,
+                            })
+                        }
+                    };
+                    name.push(escaped);
+                }
+                Some(c) => name.push(c),
+            }
+        }
+    }
+
+    // The four hex digits after `\u`, plus a second `\uXXXX` for a surrogate
+    // pair. `position` is where the escape started.
+    fn parse_unicode_escape(&mut self, position: usize) -> Result<char, SelectorError> {
+        let invalid = SelectorError {
+            position,
+            kind: SelectorErrorKind::InvalidEscape,
+        };
+        let high = self.parse_hex4().ok_or(invalid.clone())?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            if !self.selector[self.pos..].starts_with("\\u") {
+                return Err(invalid);
+            }
+            self.pos += 2;
+            let low = self.parse_hex4().ok_or(invalid.clone())?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(invalid);
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or(invalid)
+    }
+
+    fn parse_hex4(&mut self) -> Option<u32> {
+        let digits = self.selector.get(self.pos..self.pos + 4)?;
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        self.pos += 4;
+        u32::from_str_radix(digits, 16).ok()
+    }
+}
+
+// Characters allowed in a member name after a `.`
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-' || (!c.is_ascii() && !c.is_whitespace())
+}
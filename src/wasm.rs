@@ -0,0 +1,548 @@
+use js_sys::{Array, JsString};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::{
+    Disclosure, DuplicateKeys, HeaderRule, HttpPolicy, KeyMode, OffsetUnit, Options, Part,
+    PlaceholderStyle, Policy, RequestPolicy, Rule, Segment, Selector, ValueKind, WhitespaceMode,
+};
+
+// Convert a JS array of key sequences into segments. Each entry is either a
+// selector string (e.g. "$.contacts[0].type") or an array of keys and indices
+// (e.g. ["contacts", 0, "type"]), where entries of any other type are skipped.
+fn target_keys_from_js(target_keys_list: &Array) -> Result<Vec<Vec<Segment>>, JsError> {
+    target_keys_list
+        .iter()
+        .map(|key_array| {
+            if let Some(selector) = key_array.as_string() {
+                return Ok(Selector::parse(&selector)?.into());
+            }
+            Ok(Array::from(&key_array)
+                .iter()
+                .filter_map(|value| match value.as_f64() {
+                    Some(index) => Some(Segment::Index(index as i64)),
+                    None => value
+                        .dyn_into::<JsString>()
+                        .ok()
+                        .map(String::from)
+                        .map(Segment::Key),
+                })
+                .collect())
+        })
+        .collect()
+}
+
+fn ranges_to_js(ranges: &[[usize; 2]]) -> Array {
+    ranges
+        .iter()
+        .map(|range| {
+            Array::of2(
+                &JsValue::from(range[0] as u32),
+                &JsValue::from(range[1] as u32),
+            )
+        })
+        .collect()
+}
+
+fn ranges_from_js(ranges: &Array) -> Vec<[usize; 2]> {
+    ranges
+        .iter()
+        .map(|range| {
+            let range = Array::from(&range);
+            let bound = |i| range.get(i).as_f64().unwrap_or_default() as usize;
+            [bound(0), bound(1)]
+        })
+        .collect()
+}
+
+// Parse an offset unit name, defaulting to UTF-8 bytes when none is given
+fn offset_unit_from_js(unit: Option<String>) -> Result<OffsetUnit, JsError> {
+    match unit.as_deref() {
+        None | Some("utf8") => Ok(OffsetUnit::Utf8),
+        Some("utf16") => Ok(OffsetUnit::Utf16),
+        Some("char") => Ok(OffsetUnit::Char),
+        Some(unit) => Err(JsError::new(&format!(
+            "unknown offset unit {unit:?}, expected \"utf8\", \"utf16\" or \"char\""
+        ))),
+    }
+}
+
+// Read the options object passed to `findRanges` and `findPolicyRanges`, e.g.
+// `{ unit: "utf16", keys: "redact", whitespace: "disclosed" }`. A plain string
+// is taken as the unit.
+fn options_from_js(options: &JsValue) -> Result<Options, JsError> {
+    if options.is_undefined() || options.is_null() {
+        return Ok(Options::default());
+    }
+    if let Some(unit) = options.as_string() {
+        return Ok(Options {
+            unit: offset_unit_from_js(Some(unit))?,
+            ..Default::default()
+        });
+    }
+    let value = |name: &str| {
+        js_sys::Reflect::get(options, &JsValue::from_str(name))
+            .map_err(|_| JsError::new(&format!("cannot read option {name:?}")))
+    };
+    let field = |name: &str| -> Result<Option<String>, JsError> { Ok(value(name)?.as_string()) };
+    let keys = match field("keys")?.as_deref() {
+        None | Some("reveal") => KeyMode::Reveal,
+        Some("redact") => KeyMode::Redact,
+        Some("hideEntries") => KeyMode::HideEntries,
+        Some(keys) => {
+            return Err(JsError::new(&format!(
+                "unknown key mode {keys:?}, expected \"reveal\", \"redact\" or \"hideEntries\""
+            )))
+        }
+    };
+    let whitespace = match field("whitespace")?.as_deref() {
+        None | Some("reveal") => WhitespaceMode::Reveal,
+        Some("redact") => WhitespaceMode::Redact,
+        Some("disclosed") => WhitespaceMode::Disclosed,
+        Some(whitespace) => {
+            return Err(JsError::new(&format!(
+                "unknown whitespace mode {whitespace:?}, expected \"reveal\", \"redact\" or \"disclosed\""
+            )))
+        }
+    };
+    let duplicates = match field("duplicates")?.as_deref() {
+        None | Some("revealAll") => DuplicateKeys::RevealAll,
+        Some("first") => DuplicateKeys::First,
+        Some("last") => DuplicateKeys::Last,
+        Some("error") => DuplicateKeys::Error,
+        Some(duplicates) => {
+            return Err(JsError::new(&format!(
+                "unknown duplicate key mode {duplicates:?}, expected \"revealAll\", \"first\", \"last\" or \"error\""
+            )))
+        }
+    };
+    Ok(Options {
+        unit: offset_unit_from_js(field("unit")?)?,
+        keys,
+        whitespace,
+        strict: value("strict")?.as_bool().unwrap_or_default(),
+        duplicates,
+    })
+}
+
+// Whether the options set `name: true`
+fn flag(options: &JsValue, name: &str) -> bool {
+    options.is_object()
+        && js_sys::Reflect::get(options, &JsValue::from_str(name))
+            .ok()
+            .and_then(|value| value.as_bool())
+            .unwrap_or_default()
+}
+
+// Find the ranges for `policy`, throwing on malformed JSON when the options
+// set `rejectMalformed: true`
+fn policy_ranges_to_js(json: &str, policy: &Policy, options: &JsValue) -> Result<Array, JsError> {
+    let ranges = if flag(options, "rejectMalformed") {
+        crate::try_find_policy_ranges(json, policy, &options_from_js(options)?)?
+    } else {
+        crate::find_policy_ranges(json, policy, &options_from_js(options)?)
+    };
+    Ok(ranges_to_js(&ranges))
+}
+
+// `options` is an object with an optional `unit`, one of "utf8" (default),
+// "utf16" (JavaScript string indices) or "char", an optional `keys`, one of
+// "reveal" (default), "redact" or "hideEntries", an optional `whitespace`, one
+// of "reveal" (default), "redact" or "disclosed", an optional `strict` to
+// require valid RFC 8259 JSON and disclose nothing otherwise, and an optional
+// `rejectMalformed` to throw on malformed JSON instead of disclosing what comes
+// before the error
+#[wasm_bindgen(js_name = findRanges)]
+pub fn find_ranges(
+    json: &str,
+    target_keys_list: Array,
+    options: JsValue,
+) -> Result<Array, JsError> {
+    let target_keys = target_keys_from_js(&target_keys_list)?;
+    policy_ranges_to_js(json, &Policy::allow(&target_keys), &options)
+}
+
+// Read a rule of `findPolicyRanges`: a key sequence as read by
+// `target_keys_from_js`, or an object with the key sequence in `path`. A
+// revealing object may reveal only part of a value with one of `prefix`,
+// `suffix`, `chars` (`[start, end]`) or `regex`, e.g. `{ path: "$.phone",
+// suffix: 4 }`, and a redacting one may keep the quotes of strings with
+// `keepQuotes: true`.
+fn rule_from_js(rule: &JsValue, disclosure: Disclosure) -> Result<Rule, JsError> {
+    if !rule.is_object() || Array::is_array(rule) {
+        return Ok(Rule {
+            path: target_keys_from_js(&Array::of1(rule))?.remove(0),
+            disclosure,
+            part: None,
+        });
+    }
+    let field = |name: &str| {
+        js_sys::Reflect::get(rule, &JsValue::from_str(name))
+            .map_err(|_| JsError::new(&format!("cannot read rule field {name:?}")))
+    };
+    let path = target_keys_from_js(&Array::of1(&field("path")?))?.remove(0);
+    if disclosure == Disclosure::Redact {
+        let disclosure = match field("keepQuotes")?.as_bool() {
+            Some(true) => Disclosure::RedactContent,
+            _ => Disclosure::Redact,
+        };
+        return Ok(Rule {
+            path,
+            disclosure,
+            part: None,
+        });
+    }
+    let count = |value: JsValue| value.as_f64().map(|count| count as usize);
+    let part = if let Some(n) = count(field("prefix")?) {
+        Some(Part::Prefix(n))
+    } else if let Some(n) = count(field("suffix")?) {
+        Some(Part::Suffix(n))
+    } else if let Ok(chars) = field("chars")?.dyn_into::<Array>() {
+        let bound = |i| count(chars.get(i)).unwrap_or_default();
+        Some(Part::Chars(bound(0), bound(1)))
+    } else if let Some(pattern) = field("regex")?.as_string() {
+        Some(Part::capture(&pattern).map_err(|error| JsError::new(&error.to_string()))?)
+    } else {
+        None
+    };
+    Ok(Rule {
+        path,
+        disclosure,
+        part,
+    })
+}
+
+// Reveal the values matched by `reveal` and redact those matched by `redact`,
+// with the closest match to a value taking precedence and redaction winning
+// ties. Values matched by neither are revealed when `revealByDefault` is true,
+// so `findPolicyRanges(json, [], deny, true)` is a deny-list. Rules are read
+// by `rule_from_js`.
+#[wasm_bindgen(js_name = findPolicyRanges)]
+pub fn find_policy_ranges(
+    json: &str,
+    reveal: Array,
+    redact: Array,
+    reveal_by_default: bool,
+    options: JsValue,
+) -> Result<Array, JsError> {
+    let policy = policy_from_js(&reveal, &redact, reveal_by_default)?;
+    policy_ranges_to_js(json, &policy, &options)
+}
+
+// Like `findPolicyRanges`, returning `{ ranges, redacted, matches, duplicates }`
+// and throwing on malformed JSON. `matches` holds an array per rule, reveal
+// rules first, of `{ span, kind, path }`, with `kind` one of "object",
+// "array", "string", "number", "bool" or "null" and `path` the keys and
+// indices leading to the value. Each duplicate is `{ object, name, keys,
+// kept }`, with `object` the path to the object and `kept` the index of the
+// occurrence the rules applied to, or undefined.
+#[wasm_bindgen(js_name = findReport)]
+pub fn find_report(
+    json: &str,
+    reveal: Array,
+    redact: Array,
+    reveal_by_default: bool,
+    options: JsValue,
+) -> Result<JsValue, JsError> {
+    let policy = policy_from_js(&reveal, &redact, reveal_by_default)?;
+    let report = crate::find_report(json, &policy, &options_from_js(&options)?)?;
+    let object = |fields: &[(&str, JsValue)]| -> Result<JsValue, JsError> {
+        let object = js_sys::Object::new();
+        for (name, value) in fields {
+            js_sys::Reflect::set(&object, &JsValue::from_str(name), value)
+                .map_err(|_| JsError::new(&format!("cannot set field {name:?}")))?;
+        }
+        Ok(object.into())
+    };
+    let duplicates = report
+        .duplicates
+        .iter()
+        .map(|duplicate| {
+            object(&[
+                ("object", segments_to_js(&duplicate.object).into()),
+                ("name", JsValue::from_str(&duplicate.name)),
+                ("keys", ranges_to_js(&duplicate.keys).into()),
+                (
+                    "kept",
+                    duplicate
+                        .kept
+                        .map_or(JsValue::UNDEFINED, |kept| JsValue::from(kept as u32)),
+                ),
+            ])
+        })
+        .collect::<Result<Array, JsError>>()?;
+    let matches = report
+        .matches
+        .iter()
+        .map(|matches| {
+            matches
+                .iter()
+                .map(|found| {
+                    let kind = match found.kind {
+                        ValueKind::Object => "object",
+                        ValueKind::Array => "array",
+                        ValueKind::String => "string",
+                        ValueKind::Number => "number",
+                        ValueKind::Bool => "bool",
+                        ValueKind::Null => "null",
+                    };
+                    object(&[
+                        (
+                            "span",
+                            Array::of2(
+                                &JsValue::from(found.span[0] as u32),
+                                &JsValue::from(found.span[1] as u32),
+                            )
+                            .into(),
+                        ),
+                        ("kind", JsValue::from_str(kind)),
+                        ("path", segments_to_js(&found.path).into()),
+                    ])
+                })
+                .collect::<Result<Array, JsError>>()
+        })
+        .collect::<Result<Array, JsError>>()?;
+    object(&[
+        ("ranges", ranges_to_js(&report.ranges).into()),
+        ("redacted", ranges_to_js(&report.redacted).into()),
+        ("matches", matches.into()),
+        ("duplicates", duplicates.into()),
+    ])
+}
+
+// Convert a path of keys and indices to a JS array of strings and numbers
+fn segments_to_js(segments: &[Segment]) -> Array {
+    segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Key(key) => JsValue::from_str(key),
+            Segment::Index(index) => JsValue::from(*index as f64),
+            _ => JsValue::UNDEFINED,
+        })
+        .collect()
+}
+
+// Build the policy of `findPolicyRanges`
+fn policy_from_js(
+    reveal: &Array,
+    redact: &Array,
+    reveal_by_default: bool,
+) -> Result<Policy, JsError> {
+    let rules = |target_keys_list: &Array, disclosure| -> Result<Vec<Rule>, JsError> {
+        target_keys_list
+            .iter()
+            .map(|rule| rule_from_js(&rule, disclosure))
+            .collect()
+    };
+    Ok(Policy {
+        default: if reveal_by_default {
+            Disclosure::Reveal
+        } else {
+            Disclosure::Redact
+        },
+        rules: [
+            rules(reveal, Disclosure::Reveal)?,
+            rules(redact, Disclosure::Redact)?,
+        ]
+        .concat(),
+    })
+}
+
+// Read a header rule of `findResponseRanges`: a header name to reveal, or an
+// object such as `{ name: "Content-Type", contains: "json" }` revealing the
+// named header when its value contains `contains`, or redacting it with
+// `redact: true`
+fn header_rule_from_js(rule: &JsValue) -> Result<HeaderRule, JsError> {
+    if let Some(name) = rule.as_string() {
+        return Ok(HeaderRule::reveal(&name));
+    }
+    let field = |name: &str| {
+        js_sys::Reflect::get(rule, &JsValue::from_str(name))
+            .map_err(|_| JsError::new(&format!("cannot read header rule field {name:?}")))
+    };
+    let name = field("name")?
+        .as_string()
+        .ok_or_else(|| JsError::new("header rule without a name"))?;
+    let rule = match field("redact")?.as_bool() {
+        Some(true) => HeaderRule::redact(&name),
+        _ => HeaderRule::reveal(&name),
+    };
+    Ok(match field("contains")?.as_string() {
+        Some(needle) => rule.containing(&needle),
+        None => rule,
+    })
+}
+
+// Find the byte ranges of an HTTP/1.1 response transcript to keep, disclosing
+// header values according to the rules in `headers`, read by
+// `header_rule_from_js`, and the JSON body like `findPolicyRanges`. Options
+// are those of `findPolicyRanges`, with `unit` ignored as transcript offsets
+// are always bytes, plus `revealHeadersByDefault` to reveal the values of
+// headers no rule matches.
+#[wasm_bindgen(js_name = findResponseRanges)]
+pub fn find_response_ranges(
+    transcript: &[u8],
+    headers: Array,
+    reveal: Array,
+    redact: Array,
+    reveal_by_default: bool,
+    options: JsValue,
+) -> Result<Array, JsError> {
+    let mut policy = HttpPolicy::new(policy_from_js(&reveal, &redact, reveal_by_default)?);
+    for rule in headers.iter() {
+        policy = policy.header(header_rule_from_js(&rule)?);
+    }
+    if flag(&options, "revealHeadersByDefault") {
+        policy.headers.default = Disclosure::Reveal;
+    }
+    let ranges = if flag(&options, "rejectMalformed") {
+        crate::try_find_response_ranges(transcript, &policy, &options_from_js(&options)?)?
+    } else {
+        crate::find_response_ranges(transcript, &policy, &options_from_js(&options)?)
+    };
+    Ok(ranges_to_js(&ranges))
+}
+
+// Like `findResponseRanges` for an HTTP/1.1 request transcript, revealing the
+// path segments at the indices in `path` and the values of the query
+// parameters named in `query`. `Authorization`, `Proxy-Authorization` and
+// `Cookie` stay redacted under `revealHeadersByDefault` unless a rule in
+// `headers` names them.
+#[wasm_bindgen(js_name = findRequestRanges)]
+#[allow(clippy::too_many_arguments)]
+pub fn find_request_ranges(
+    transcript: &[u8],
+    path: Array,
+    query: Array,
+    headers: Array,
+    reveal: Array,
+    redact: Array,
+    reveal_by_default: bool,
+    options: JsValue,
+) -> Result<Array, JsError> {
+    let mut policy = RequestPolicy::new(policy_from_js(&reveal, &redact, reveal_by_default)?);
+    policy.path = path
+        .iter()
+        .filter_map(|index| index.as_f64())
+        .map(|index| index as usize)
+        .collect();
+    policy.query = query.iter().filter_map(|name| name.as_string()).collect();
+    for rule in headers.iter() {
+        policy = policy.header(header_rule_from_js(&rule)?);
+    }
+    if flag(&options, "revealHeadersByDefault") {
+        policy.headers.default = Disclosure::Reveal;
+    }
+    let ranges = if flag(&options, "rejectMalformed") {
+        crate::try_find_request_ranges(transcript, &policy, &options_from_js(&options)?)?
+    } else {
+        crate::find_request_ranges(transcript, &policy, &options_from_js(&options)?)
+    };
+    Ok(ranges_to_js(&ranges))
+}
+
+#[wasm_bindgen(js_name = convertRanges)]
+pub fn convert_ranges(
+    text: &str,
+    ranges: Array,
+    from: Option<String>,
+    to: Option<String>,
+) -> Result<Array, JsError> {
+    Ok(ranges_to_js(&crate::convert_ranges(
+        text,
+        &ranges_from_js(&ranges),
+        offset_unit_from_js(from)?,
+        offset_unit_from_js(to)?,
+    )))
+}
+
+#[wasm_bindgen(js_name = redactJson)]
+pub fn redact_json(json: &str, ranges: Array, unit: Option<String>) -> Result<String, JsError> {
+    let ranges = crate::convert_ranges(
+        json,
+        &ranges_from_js(&ranges),
+        offset_unit_from_js(unit)?,
+        OffsetUnit::Utf8,
+    );
+    Ok(crate::redact_json(json, clamp_ranges(json, ranges)))
+}
+
+// Sort and merge `ranges`, clamped to `json` with boundaries inside multi-byte
+// characters moved inwards, as `redact_json` slices `json` at them
+fn clamp_ranges(json: &str, ranges: Vec<[usize; 2]>) -> Vec<[usize; 2]> {
+    let ranges = ranges
+        .into_iter()
+        .map(|[start, end]| {
+            let mut start = start.min(json.len());
+            let mut end = end.min(json.len());
+            while !json.is_char_boundary(start) {
+                start += 1;
+            }
+            while !json.is_char_boundary(end) {
+                end -= 1;
+            }
+            [start, end]
+        })
+        .collect();
+    crate::ranges::merge_ranges(ranges)
+}
+
+// `filler` is a single ASCII character, "X" by default
+#[wasm_bindgen(js_name = redactJsonFilled)]
+pub fn redact_json_filled(
+    json: &str,
+    ranges: Array,
+    filler: Option<String>,
+    unit: Option<String>,
+) -> Result<String, JsError> {
+    let filler = match filler.as_deref().map(str::as_bytes) {
+        None => b'X',
+        Some(&[filler]) if filler.is_ascii() => filler,
+        Some(_) => return Err(JsError::new("filler must be a single ASCII character")),
+    };
+    let ranges = crate::convert_ranges(
+        json,
+        &ranges_from_js(&ranges),
+        offset_unit_from_js(unit)?,
+        OffsetUnit::Utf8,
+    );
+    Ok(crate::redact_json_filled(json, &ranges, filler))
+}
+
+// `style` is "marker" (the default) or "typed", which keeps the type and length
+// of hidden values and fills hidden strings with `filler`, "X" by default
+#[wasm_bindgen(js_name = redactJsonWith)]
+pub fn redact_json_with(
+    json: &str,
+    ranges: Array,
+    style: Option<String>,
+    filler: Option<String>,
+    unit: Option<String>,
+) -> Result<String, JsError> {
+    let style = match style.as_deref() {
+        None | Some("marker") => PlaceholderStyle::Marker,
+        Some("typed") => PlaceholderStyle::Typed {
+            filler: match filler.as_deref().map(str::as_bytes) {
+                None => b'X',
+                Some(&[filler]) if filler.is_ascii() && filler != b'"' && filler != b'\\' => filler,
+                Some(_) => {
+                    return Err(JsError::new(
+                        "filler must be a single ASCII character other than a quote or backslash",
+                    ))
+                }
+            },
+        },
+        Some(style) => {
+            return Err(JsError::new(&format!(
+                "unknown placeholder style {style:?}"
+            )))
+        }
+    };
+    let ranges = crate::convert_ranges(
+        json,
+        &ranges_from_js(&ranges),
+        offset_unit_from_js(unit)?,
+        OffsetUnit::Utf8,
+    );
+    Ok(crate::redact_json_with(json, &ranges, style))
+}
@@ -0,0 +1,21 @@
+use json_str_redactor::{HttpError, HttpErrorKind, ParseError, ParseErrorKind};
+
+// The position and kind of the error a transcript was rejected with
+pub fn error(result: Result<Vec<[usize; 2]>, HttpError>) -> (usize, HttpErrorKind) {
+    let HttpError { position, kind } = result.unwrap_err();
+    (position, kind)
+}
+
+pub fn body_error(
+    position: usize,
+    line: usize,
+    column: usize,
+    kind: ParseErrorKind,
+) -> HttpErrorKind {
+    HttpErrorKind::Body(ParseError {
+        position,
+        line,
+        column,
+        kind,
+    })
+}
@@ -0,0 +1,60 @@
+use json_str_redactor::{find_ranges, redact_json, Segment, Selector};
+
+const REPO: &str = r#"{"id":1,"owner":{"login":"octocat","id":2,"site_admin":false},"forks":[{"id":3,"owner":{"login":"hubot","id":4}}],"name":"hello"}"#;
+
+fn redact(json: &str, selector: &str) -> String {
+    redact_json(
+        json,
+        find_ranges(json, &[Selector::parse(selector).unwrap()]),
+    )
+}
+
+#[test]
+fn parses_descendant_segments() {
+    assert_eq!(
+        Selector::parse("$..login").unwrap().segments(),
+        [Segment::Descendants, Segment::from("login")]
+    );
+    assert_eq!(
+        Selector::parse("$.forks..[0]..*").unwrap().segments(),
+        [
+            Segment::from("forks"),
+            Segment::Descendants,
+            Segment::Index(0),
+            Segment::Descendants,
+            Segment::Wildcard,
+        ]
+    );
+}
+
+#[test]
+fn matches_a_key_at_any_depth() {
+    assert_eq!(
+        redact(REPO, "$..login"),
+        r#"{"id":"<REDACTED>","owner":{"login":"octocat","id":"<REDACTED>","site_admin":"<REDACTED>"},"forks":[{"id":"<REDACTED>","owner":{"login":"hubot","id":"<REDACTED>"}}],"name":"<REDACTED>"}"#
+    );
+    assert_eq!(
+        redact(REPO, "$..id"),
+        r#"{"id":1,"owner":{"login":"<REDACTED>","id":2,"site_admin":"<REDACTED>"},"forks":[{"id":3,"owner":{"login":"<REDACTED>","id":4}}],"name":"<REDACTED>"}"#
+    );
+}
+
+#[test]
+fn matches_only_below_the_prefix() {
+    assert_eq!(
+        redact(REPO, "$.forks..id"),
+        r#"{"id":"<REDACTED>","owner":{"login":"<REDACTED>","id":"<REDACTED>","site_admin":"<REDACTED>"},"forks":[{"id":3,"owner":{"login":"<REDACTED>","id":4}}],"name":"<REDACTED>"}"#
+    );
+    assert_eq!(
+        redact(REPO, "$.forks..owner.id"),
+        r#"{"id":"<REDACTED>","owner":{"login":"<REDACTED>","id":"<REDACTED>","site_admin":"<REDACTED>"},"forks":[{"id":"<REDACTED>","owner":{"login":"<REDACTED>","id":4}}],"name":"<REDACTED>"}"#
+    );
+}
+
+#[test]
+fn descendant_wildcard_discloses_everything_below() {
+    assert_eq!(
+        find_ranges(REPO, &[Selector::parse("$..*").unwrap()]),
+        [[0, REPO.len()]]
+    );
+}
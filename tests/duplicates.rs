@@ -0,0 +1,129 @@
+use json_str_redactor::{
+    find_policy_ranges, find_ranges_with, find_report, redact_json_with, try_find_ranges_with,
+    DuplicateKey, DuplicateKeys, Options, ParseErrorKind, PlaceholderStyle, Policy, Segment,
+    Selector,
+};
+
+const JSON: &str =
+    r#"{"id":1,"to":"alice","items":[{"to":"x"},{"n":1,"n":2,"n":3}],"to":"mallory"}"#;
+
+fn duplicates(duplicates: DuplicateKeys) -> Options {
+    Options {
+        duplicates,
+        ..Default::default()
+    }
+}
+
+fn render(policy: &Policy, mode: DuplicateKeys) -> String {
+    let ranges = find_policy_ranges(JSON, policy, &duplicates(mode));
+    redact_json_with(JSON, &ranges, PlaceholderStyle::Marker)
+}
+
+#[test]
+fn every_occurrence_is_revealed_by_default() {
+    let policy = Policy::allow(&[vec!["to"]]);
+    assert_eq!(
+        render(&policy, DuplicateKeys::RevealAll),
+        r#"{"id":"<REDACTED>","to":"alice","items":[{"to":"<REDACTED>"},{"n":"<REDACTED>","n":"<REDACTED>","n":"<REDACTED>"}],"to":"mallory"}"#
+    );
+    assert_eq!(
+        find_ranges_with(JSON, &[vec!["to"]], &Options::default()),
+        find_ranges_with(JSON, &[vec!["to"]], &duplicates(DuplicateKeys::RevealAll))
+    );
+}
+
+#[test]
+fn only_the_first_or_last_occurrence_follows_the_rules() {
+    let policy = Policy::allow(&[vec!["to"], vec!["items", "n"]]);
+    assert_eq!(
+        render(&policy, DuplicateKeys::First),
+        r#"{"id":"<REDACTED>","to":"alice","items":[{"to":"<REDACTED>"},{"n":1,"n":"<REDACTED>","n":"<REDACTED>"}],"to":"<REDACTED>"}"#
+    );
+    assert_eq!(
+        render(&policy, DuplicateKeys::Last),
+        r#"{"id":"<REDACTED>","to":"<REDACTED>","items":[{"to":"<REDACTED>"},{"n":"<REDACTED>","n":"<REDACTED>","n":3}],"to":"mallory"}"#
+    );
+    // Even when the whole document is revealed
+    assert_eq!(
+        render(&Policy::deny(&[vec!["id"]]), DuplicateKeys::Last),
+        r#"{"id":"<REDACTED>","to":"<REDACTED>","items":[{"to":"x"},{"n":"<REDACTED>","n":"<REDACTED>","n":3}],"to":"mallory"}"#
+    );
+}
+
+#[test]
+fn duplicates_can_be_rejected() {
+    let options = duplicates(DuplicateKeys::Error);
+    let error = try_find_ranges_with(JSON, &[vec!["to"]], &options).unwrap_err();
+    assert_eq!(error.kind, ParseErrorKind::DuplicateKey);
+    // The second `n` comes before the second `to`
+    assert_eq!(error.position, 48);
+    assert!(find_ranges_with(JSON, &[vec!["to"]], &options).is_empty());
+    assert!(try_find_ranges_with(r#"{"a":{"b":1},"b":1}"#, &[vec!["a"]], &options).is_ok());
+
+    // An earlier lenient error does not hide the duplicate
+    let json = r#"{"x":01,"a":"alice","a":"mallory"}"#;
+    assert!(find_ranges_with(json, &[vec!["a"]], &options).is_empty());
+    let error = try_find_ranges_with(json, &[vec!["a"]], &options).unwrap_err();
+    assert_eq!(error.kind, ParseErrorKind::InvalidNumber);
+}
+
+#[test]
+fn duplicates_are_reported() {
+    let report = find_report(
+        JSON,
+        &Policy::allow(&[vec!["to"]]),
+        &duplicates(DuplicateKeys::First),
+    )
+    .unwrap();
+    assert_eq!(
+        report.duplicates,
+        [
+            DuplicateKey {
+                object: vec![],
+                name: "to".to_string(),
+                keys: vec![[8, 12], [62, 66]],
+                kept: Some(0),
+            },
+            DuplicateKey {
+                object: vec![Segment::from("items"), Segment::Index(1)],
+                name: "n".to_string(),
+                keys: vec![[42, 45], [48, 51], [54, 57]],
+                kept: Some(0),
+            },
+        ]
+    );
+    assert_eq!(
+        report.ranges,
+        find_policy_ranges(
+            JSON,
+            &Policy::allow(&[vec!["to"]]),
+            &duplicates(DuplicateKeys::First)
+        )
+    );
+    let report = find_report(JSON, &Policy::allow(&[vec!["to"]]), &Options::default()).unwrap();
+    assert!(report
+        .duplicates
+        .iter()
+        .all(|duplicate| duplicate.kept.is_none()));
+}
+
+#[test]
+fn filters_read_the_occurrence_the_rules_apply_to() {
+    let json = r#"[{"currency":"EUR","currency":"USD","amount":5}]"#;
+    let policy = Policy::allow(&[
+        Selector::parse("$[?(@.currency == 'USD')].amount").unwrap(),
+        Selector::parse("$[*].currency").unwrap(),
+    ]);
+    let render = |mode| {
+        let ranges = find_policy_ranges(json, &policy, &duplicates(mode));
+        redact_json_with(json, &ranges, PlaceholderStyle::Marker)
+    };
+    assert_eq!(
+        render(DuplicateKeys::First),
+        r#"[{"currency":"EUR","currency":"<REDACTED>","amount":"<REDACTED>"}]"#
+    );
+    assert_eq!(
+        render(DuplicateKeys::Last),
+        r#"[{"currency":"<REDACTED>","currency":"USD","amount":5}]"#
+    );
+}
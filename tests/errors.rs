@@ -0,0 +1,152 @@
+use json_str_redactor::{
+    find_ranges, find_ranges_with, redact_json_with, try_find_policy_ranges, try_find_ranges,
+    try_find_ranges_with, Options, ParseError, ParseErrorKind, PlaceholderStyle, Policy, Selector,
+    MAX_DEPTH,
+};
+
+fn error(json: &str) -> (usize, ParseErrorKind) {
+    let ParseError { position, kind, .. } = try_find_ranges(json, &[vec!["a"]]).unwrap_err();
+    (position, kind)
+}
+
+#[test]
+fn well_formed_documents_give_the_lenient_ranges() {
+    for json in [
+        r#"{"a":1,"b":[true,false,null,-0.5e+3,"x\"y"]}"#,
+        " [ ] ",
+        "\"\\\\\"",
+    ] {
+        assert_eq!(
+            try_find_ranges(json, &[vec!["a"]]),
+            Ok(find_ranges(json, &[vec!["a"]]))
+        );
+    }
+}
+
+#[test]
+fn reports_each_kind_of_error() {
+    assert_eq!(error(""), (0, ParseErrorKind::EmptyDocument));
+    assert_eq!(error("  \n"), (3, ParseErrorKind::EmptyDocument));
+    assert_eq!(error(r#"{"a":"b"#), (5, ParseErrorKind::UnterminatedString));
+    assert_eq!(
+        error(r#"{"a":"b\"}"#),
+        (5, ParseErrorKind::UnterminatedString)
+    );
+    assert_eq!(error(r#"{"a:1}"#), (1, ParseErrorKind::UnterminatedString));
+    assert_eq!(
+        error(r#"{"a":[1,2}"#),
+        (9, ParseErrorKind::UnbalancedBrackets)
+    );
+    assert_eq!(
+        error(r#"{"a":[1,2]"#),
+        (10, ParseErrorKind::UnbalancedBrackets)
+    );
+    assert_eq!(
+        error(r#"{"a":1}}"#),
+        (7, ParseErrorKind::UnbalancedBrackets)
+    );
+    assert_eq!(error("]"), (0, ParseErrorKind::UnbalancedBrackets));
+    assert_eq!(error(r#"{"a" 1}"#), (5, ParseErrorKind::UnexpectedToken));
+    assert_eq!(
+        error(r#"{"a":1 "b":2}"#),
+        (7, ParseErrorKind::UnexpectedToken)
+    );
+    assert_eq!(error(r#"[1,]"#), (3, ParseErrorKind::UnexpectedToken));
+    assert_eq!(error(r#"{"a":nul}"#), (5, ParseErrorKind::UnexpectedToken));
+    assert_eq!(error(r#"{"a":01}"#), (5, ParseErrorKind::InvalidNumber));
+    assert_eq!(error(r#"[1.,-]"#), (1, ParseErrorKind::InvalidNumber));
+    assert_eq!(error(r#"{"a":1} x"#), (8, ParseErrorKind::TrailingData));
+    assert_eq!(error(r#""a" "b""#), (4, ParseErrorKind::TrailingData));
+}
+
+#[test]
+fn the_first_error_wins() {
+    assert_eq!(error(r#"[01, "x"#), (1, ParseErrorKind::InvalidNumber));
+    assert_eq!(error(r#"[1 2, 01]"#), (3, ParseErrorKind::UnexpectedToken));
+}
+
+#[test]
+fn nothing_past_the_first_error_is_disclosed() {
+    let json = r#"{"a":1,"b":nul,"c":"secret"}"#;
+    assert_eq!(find_ranges(json, &[vec!["c"]]), [[0, 5], [6, 11]]);
+    let json = r#"{"a":01,"c":"secret"}"#;
+    assert_eq!(find_ranges(json, &[vec!["c"]]), [[0, 5]]);
+    let json = r#"{"a":"x"]"#;
+    assert_eq!(find_ranges(json, &[vec!["a"]]), [[0, 8]]);
+}
+
+#[test]
+fn positions_have_lines_and_columns() {
+    let json = "{\r\n  \"name\": \"Zoë\",\n  \"age\": 3x\n}";
+    let error = try_find_policy_ranges(
+        json,
+        &Policy::deny(&[Selector::parse("$.age").unwrap()]),
+        &Default::default(),
+    )
+    .unwrap_err();
+    assert_eq!(error.kind, ParseErrorKind::InvalidNumber);
+    assert_eq!((error.line, error.column), (3, 10));
+    assert_eq!(&json[error.position..error.position + 2], "3x");
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "invalid number at line 3, column 10 (offset {})",
+            error.position
+        )
+    );
+}
+
+fn strict() -> Options {
+    Options {
+        strict: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn strict_mode_checks_strings() {
+    let cases = [
+        ("[\"a\tb\"]", 3, ParseErrorKind::ControlCharacter),
+        (r#"{"a\q":1}"#, 3, ParseErrorKind::InvalidEscape),
+        (r#"["\u12"]"#, 2, ParseErrorKind::InvalidEscape),
+        (r#"["x\ud800"]"#, 3, ParseErrorKind::InvalidEscape),
+        (r#"["\udc00\ud800"]"#, 2, ParseErrorKind::InvalidEscape),
+    ];
+    for (json, position, kind) in cases {
+        // Lenient parsing accepts them
+        assert!(try_find_ranges(json, &[vec!["a"]]).is_ok(), "{json}");
+        let error = try_find_ranges_with(json, &[vec!["a"]], &strict()).unwrap_err();
+        assert_eq!((error.position, error.kind), (position, kind), "{json}");
+    }
+
+    let json = r#"["\"\\\/\b\f\n\r\té😀"]"#;
+    assert_eq!(
+        try_find_ranges_with(json, &[vec!["a"]], &strict()),
+        Ok(find_ranges(json, &[vec!["a"]]))
+    );
+}
+
+#[test]
+fn strict_mode_fails_closed() {
+    let json = r#"{"a":"x","b":"\q"}"#;
+    assert_eq!(find_ranges(json, &[vec!["a"]]), [[0, 13], [17, 18]]);
+    assert!(find_ranges_with(json, &[vec!["a"]], &strict()).is_empty());
+    assert!(find_ranges_with("[1,]", &[vec!["a"]], &strict()).is_empty());
+}
+
+#[test]
+fn deep_nesting_fails_closed() {
+    let json = "[".repeat(100_000) + &"]".repeat(100_000);
+    assert_eq!(error(&json), (MAX_DEPTH, ParseErrorKind::TooDeep));
+    assert!(find_ranges(&json, &[Vec::<&str>::new()]).is_empty());
+    let rendered = redact_json_with(&json, &[], PlaceholderStyle::Marker);
+    assert!(rendered.len() <= json.len());
+
+    let json = r#"{"a":"#.repeat(100_000) + "1" + &"}".repeat(100_000);
+    assert_eq!(error(&json), (5 * MAX_DEPTH, ParseErrorKind::TooDeep));
+    assert!(find_ranges(&json, &[vec!["a"]]).is_empty());
+
+    // Nesting up to the limit is accepted
+    let json = "[".repeat(MAX_DEPTH) + &"]".repeat(MAX_DEPTH);
+    assert_eq!(find_ranges(&json, &[vec!["a"]]), [[0, json.len()]]);
+}
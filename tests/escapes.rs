@@ -0,0 +1,57 @@
+use json_str_redactor::{find_ranges, redact_json};
+
+fn redact(json: &str, keys: &[Vec<&str>]) -> String {
+    redact_json(json, find_ranges(json, keys))
+}
+
+#[test]
+fn escaped_quotes_do_not_end_strings() {
+    let json = r#"{"msg":"say \"hi\": {","name":"Bob"}"#;
+    assert_eq!(
+        redact(json, &[vec!["name"]]),
+        r#"{"msg":"<REDACTED>","name":"Bob"}"#
+    );
+    assert_eq!(
+        redact(json, &[vec!["msg"]]),
+        r#"{"msg":"say \"hi\": {","name":"<REDACTED>"}"#
+    );
+}
+
+#[test]
+fn escaped_backslash_before_closing_quote() {
+    let json = r#"{"path":"C:\\","next":"x"}"#;
+    assert_eq!(
+        redact(json, &[vec!["next"]]),
+        r#"{"path":"<REDACTED>","next":"x"}"#
+    );
+}
+
+#[test]
+fn keys_are_matched_after_decoding() {
+    let json = r#"{"caf\u00e9":"open","a\"b":1,"tab\there":2,"\ud83d\ude00":3}"#;
+    assert_eq!(
+        find_ranges(json, &[vec!["café"]]),
+        [[0, 27], [28, 41], [42, 58], [59, 60]]
+    );
+    assert_eq!(
+        redact(
+            json,
+            &[vec!["café"], vec!["a\"b"], vec!["tab\there"], vec!["😀"]]
+        ),
+        json
+    );
+    // Ranges still cover the raw escaped key
+    assert_eq!(
+        redact(json, &[vec!["a\"b"]]),
+        r#"{"caf\u00e9":"<REDACTED>","a\"b":1,"tab\there":"<REDACTED>","\ud83d\ude00":"<REDACTED>"}"#
+    );
+}
+
+#[test]
+fn raw_escape_sequence_does_not_match_as_key() {
+    let json = r#"{"caf\u00e9":"open"}"#;
+    assert_eq!(
+        redact(json, &[vec![r"caf\u00e9"]]),
+        r#"{"caf\u00e9":"<REDACTED>"}"#
+    );
+}
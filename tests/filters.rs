@@ -0,0 +1,120 @@
+use json_str_redactor::{
+    find_ranges, redact_json, Comparison, Filter, Literal, Segment, Selector, SelectorErrorKind,
+};
+
+const STATEMENT: &str = r#"{"transactions":[{"id":"t1","currency":"USD","amount":12.5},{"id":"t2","currency":"EUR","amount":40},{"id":"t3","currency":"USD","amount":99,"memo":"rent"}]}"#;
+
+fn redact(json: &str, selectors: &[&str]) -> String {
+    let selectors: Vec<Selector> = selectors.iter().map(|s| s.parse().unwrap()).collect();
+    redact_json(json, find_ranges(json, &selectors))
+}
+
+#[test]
+fn parses_filter_expressions() {
+    assert_eq!(
+        Selector::parse("$.transactions[?(@.currency == 'USD')].amount")
+            .unwrap()
+            .segments(),
+        [
+            Segment::from("transactions"),
+            Segment::Filter(Filter {
+                path: vec![Segment::from("currency")],
+                condition: Some((Comparison::Eq, Literal::String("USD".into()))),
+            }),
+            Segment::from("amount"),
+        ]
+    );
+    assert_eq!(
+        Selector::parse("$[?@['a'][0]>=-1.5e2]").unwrap().segments(),
+        [Segment::Filter(Filter {
+            path: vec![Segment::from("a"), Segment::Index(0)],
+            condition: Some((Comparison::Ge, Literal::Number(-150.0))),
+        })]
+    );
+    assert_eq!(
+        Selector::parse("$[?(@.memo)]").unwrap().segments(),
+        [Segment::Filter(Filter {
+            path: vec![Segment::from("memo")],
+            condition: None,
+        })]
+    );
+}
+
+#[test]
+fn reports_filter_errors() {
+    let kind = |selector: &str| {
+        let error = Selector::parse(selector).unwrap_err();
+        (error.position, error.kind)
+    };
+    assert_eq!(
+        kind("$[?(.a == 1)]"),
+        (4, SelectorErrorKind::MissingCurrent)
+    );
+    assert_eq!(
+        kind("$[?(@.a = 1)]"),
+        (8, SelectorErrorKind::UnexpectedChar('='))
+    );
+    assert_eq!(
+        kind("$[?(@.a == USD)]"),
+        (11, SelectorErrorKind::InvalidLiteral)
+    );
+    assert_eq!(
+        kind("$[?(@.a == 01)]"),
+        (11, SelectorErrorKind::InvalidLiteral)
+    );
+    assert_eq!(
+        kind("$[?(@.a == 1]"),
+        (12, SelectorErrorKind::UnexpectedChar(']'))
+    );
+}
+
+#[test]
+fn filters_on_string_equality() {
+    assert_eq!(
+        redact(
+            STATEMENT,
+            &["$.transactions[?(@.currency == 'USD')].amount"]
+        ),
+        r#"{"transactions":[{"id":"<REDACTED>","currency":"<REDACTED>","amount":12.5},{"id":"<REDACTED>","currency":"<REDACTED>","amount":"<REDACTED>"},{"id":"<REDACTED>","currency":"<REDACTED>","amount":99,"memo":"<REDACTED>"}]}"#
+    );
+    assert_eq!(
+        redact(STATEMENT, &["$.transactions[?(@.currency != 'USD')]"]),
+        r#"{"transactions":[{"id":"<REDACTED>","currency":"<REDACTED>","amount":"<REDACTED>"},{"id":"t2","currency":"EUR","amount":40},{"id":"<REDACTED>","currency":"<REDACTED>","amount":"<REDACTED>","memo":"<REDACTED>"}]}"#
+    );
+}
+
+#[test]
+fn filters_on_numbers_and_existence() {
+    assert_eq!(
+        redact(STATEMENT, &["$.transactions[?(@.amount > 40)].id"]),
+        redact(STATEMENT, &["$.transactions[2].id"])
+    );
+    assert_eq!(
+        redact(STATEMENT, &["$.transactions[?(@.amount <= 40.0)].id"]),
+        redact(STATEMENT, &["$.transactions[:2].id"])
+    );
+    assert_eq!(
+        redact(STATEMENT, &["$.transactions[?(@.memo)].id"]),
+        redact(STATEMENT, &["$.transactions[-1].id"])
+    );
+    // Values of a different type never compare equal
+    assert_eq!(
+        redact(STATEMENT, &["$.transactions[?(@.amount == '40')]"]),
+        redact(STATEMENT, &[])
+    );
+}
+
+#[test]
+fn filters_scalar_elements_and_escaped_strings() {
+    let json = r#"{"contacts":[{"type":"email","value":"a@b.c"},{"type":"phone","value":"123"}],"scores":[3,10,7]}"#;
+    assert_eq!(
+        redact(
+            json,
+            &[
+                "$.contacts[?(@.type == \"email\")].value",
+                "$.scores[?(@ >= 7)]"
+            ]
+        ),
+        r#"{"contacts":[{"type":"<REDACTED>","value":"a@b.c"},{"type":"<REDACTED>","value":"<REDACTED>"}],"scores":["<REDACTED>",10,7]}"#
+    );
+}
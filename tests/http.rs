@@ -0,0 +1,317 @@
+mod common;
+
+use common::body_error;
+use json_str_redactor::{
+    find_policy_ranges, find_response_ranges, try_find_response_ranges, Disclosure, HeaderRule,
+    HttpErrorKind, HttpPolicy, Options, ParseErrorKind, Policy,
+};
+
+const BODY: &str = r#"{"id":7,"name":"Zoë"}"#;
+
+fn response(headers: &str, body: &str) -> Vec<u8> {
+    format!("HTTP/1.1 200 OK\r\n{headers}\r\n{body}").into_bytes()
+}
+
+fn error(transcript: &[u8]) -> (usize, HttpErrorKind) {
+    let policy = HttpPolicy::new(Policy::allow(&[vec!["id"]]));
+    common::error(try_find_response_ranges(
+        transcript,
+        &policy,
+        &Options::default(),
+    ))
+}
+
+#[test]
+fn header_values_are_redacted_unless_revealed() {
+    let transcript = response(
+        "Date: Sun, 18 Oct 2026\r\ncontent-type:  application/json \r\nSet-Cookie: s=1\r\n",
+        BODY,
+    );
+    let policy =
+        HttpPolicy::new(Policy::allow(&[vec!["id"]])).reveal_headers(&["DATE", "Content-Type"]);
+    let ranges = find_response_ranges(&transcript, &policy, &Options::default());
+    assert_eq!(ranges, [[0, 87], [90, 109], [115, 116]]);
+    assert_eq!(&transcript[87..90], b"s=1");
+    assert_eq!(
+        try_find_response_ranges(&transcript, &policy, &Options::default()),
+        Ok(ranges)
+    );
+}
+
+#[test]
+fn body_ranges_are_shifted_into_the_transcript() {
+    let transcript = response(&format!("Content-Length: {}\r\n", BODY.len()), BODY);
+    let start = transcript.len() - BODY.len();
+    let policy = HttpPolicy::new(Policy::deny(&[vec!["name"]])).reveal_headers(&["content-length"]);
+    let ranges = find_response_ranges(&transcript, &policy, &Options::default());
+    let body_ranges = find_policy_ranges(BODY, &policy.body, &Options::default());
+    assert_eq!(ranges[0], [0, start + body_ranges[0][1]]);
+    assert_eq!(ranges[1], [start + body_ranges[1][0], transcript.len()]);
+    assert_eq!(ranges.len(), 2);
+}
+
+#[test]
+fn empty_bodies_are_not_malformed() {
+    let policy = HttpPolicy::new(Policy::allow(&[vec!["id"]]));
+    let strict = Options {
+        strict: true,
+        ..Default::default()
+    };
+    let transcript = b"HTTP/1.1 204 No Content\r\nDate: today\r\n\r\n";
+    assert_eq!(
+        try_find_response_ranges(transcript, &policy, &Options::default()),
+        Ok(vec![[0, 31], [36, 40]])
+    );
+    assert_eq!(
+        find_response_ranges(transcript, &policy, &strict),
+        [[0, 31], [36, 40]]
+    );
+
+    let transcript = response("Content-Length: 0\r\n", "");
+    assert_eq!(
+        find_response_ranges(&transcript, &policy, &strict),
+        [[0, 33], [34, 38]]
+    );
+}
+
+#[test]
+fn bytes_past_the_content_length_are_not_disclosed() {
+    let transcript = response("Content-Length: 2\r\n", "{}HTTP/1.1 200 OK");
+    let policy = HttpPolicy::new(Policy::deny(&[vec!["x"]]));
+    let ranges = find_response_ranges(&transcript, &policy, &Options::default());
+    assert_eq!(ranges, [[0, 33], [34, 40]]);
+    assert_eq!(&transcript[38..40], b"{}");
+}
+
+#[test]
+fn reports_malformed_transcripts() {
+    assert_eq!(
+        error(b"HTTP/2 200 OK\r\n\r\n{}"),
+        (0, HttpErrorKind::InvalidStartLine)
+    );
+    // A bare LF would otherwise keep the header as part of the status line
+    assert_eq!(
+        error(b"HTTP/1.1 200 OK\nSet-Cookie: session=SECRET\r\nContent-Length: 2\r\n\r\n{}"),
+        (0, HttpErrorKind::InvalidStartLine)
+    );
+    assert_eq!(
+        error(b"HTTP/1.1 200 OK\nDate: x\n\n{\"a\":\"\r\n\r\n\"}"),
+        (0, HttpErrorKind::InvalidStartLine)
+    );
+    assert_eq!(
+        error(b"HTTP/1.1 200\r\nBad Name: x\r\n\r\n{}"),
+        (17, HttpErrorKind::InvalidHeader)
+    );
+    assert_eq!(
+        error(b"HTTP/1.1 200 OK\r\nA: 1\r\n folded\r\n\r\n{}"),
+        (23, HttpErrorKind::InvalidHeader)
+    );
+    assert_eq!(
+        error(b"HTTP/1.1 200 OK\r\nA: 1\r\n"),
+        (23, HttpErrorKind::UnterminatedHead)
+    );
+    assert_eq!(
+        error(&response(
+            "Content-Length: 2\r\nContent-Length: 3\r\n",
+            "{}"
+        )),
+        (52, HttpErrorKind::InvalidContentLength)
+    );
+    assert_eq!(
+        error(&response("Content-Length: 5\r\n", "{}")),
+        (40, HttpErrorKind::IncompleteBody)
+    );
+    assert_eq!(
+        error(&response("Content-Encoding: gzip\r\n", "{}")),
+        (35, HttpErrorKind::UnsupportedEncoding)
+    );
+    assert_eq!(
+        error(&[&response("", "[\"")[..], &[0xff], b"\"]"].concat()),
+        (21, HttpErrorKind::InvalidUtf8)
+    );
+    assert_eq!(
+        error(&response("", "{\"id\":}")),
+        (25, body_error(25, 3, 7, ParseErrorKind::UnexpectedToken))
+    );
+    let policy = HttpPolicy::new(Policy::allow(&[vec!["id"]]));
+    let error = try_find_response_ranges(&response("", "{\"id\":}"), &policy, &Options::default())
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "malformed JSON body: unexpected token at line 3, column 7 (offset 25)"
+    );
+}
+
+#[test]
+fn malformed_transcripts_are_handled_leniently() {
+    let policy = HttpPolicy::new(Policy::deny(&[vec!["x"]]));
+    let options = Options::default();
+    assert!(find_response_ranges(b"HTTP/1.1 200 OK\r\n", &policy, &options).is_empty());
+
+    // A truncated body is disclosed up to the point where it stops
+    let transcript = response("Content-Length: 30\r\n", r#"{"a":1,"b":"#);
+    let ranges = find_response_ranges(&transcript, &policy, &options);
+    assert_eq!(ranges, [[0, 33], [35, 50]]);
+
+    // Nor past an error that parsing goes on after
+    let transcript = response("Content-Length: 28\r\n", r#"{"a":1,"b":nul,"c":"secret"}"#);
+    let ranges = find_response_ranges(&transcript, &policy, &options);
+    assert_eq!(ranges, [[0, 33], [35, 50]]);
+
+    // Strict mode discloses nothing
+    let options = Options {
+        strict: true,
+        ..Default::default()
+    };
+    assert!(find_response_ranges(&transcript, &policy, &options).is_empty());
+}
+
+fn chunked(chunks: &[&[u8]], trailers: &str) -> Vec<u8> {
+    let mut transcript = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n".to_vec();
+    for chunk in chunks {
+        transcript.extend(format!("{:x}\r\n", chunk.len()).bytes());
+        transcript.extend(*chunk);
+        transcript.extend(b"\r\n");
+    }
+    transcript.extend(format!("0\r\n{trailers}\r\n").bytes());
+    transcript
+}
+
+#[test]
+fn chunked_bodies_are_decoded() {
+    // `ë` straddles the last two chunks
+    let body = BODY.as_bytes();
+    let transcript = chunked(&[&body[..10], &body[10..19], &body[19..]], "X-Sig: abc\r\n");
+    let policy =
+        HttpPolicy::new(Policy::deny(&[vec!["name"]])).reveal_headers(&["transfer-encoding"]);
+    let ranges = find_response_ranges(&transcript, &policy, &Options::default());
+    // The redacted name is split around the chunk size line, and the trailer
+    // value is redacted like a header value
+    assert_eq!(ranges, [[0, 70], [74, 79], [81, 94], [97, 101]]);
+    assert_eq!(&transcript[70..74], b"\"Zo\xc3");
+    assert_eq!(&transcript[74..79], b"\r\n3\r\n");
+    assert_eq!(&transcript[79..81], b"\xab\"");
+    assert_eq!(transcript.len(), 101);
+    assert_eq!(
+        try_find_response_ranges(&transcript, &policy, &Options::default()),
+        Ok(ranges)
+    );
+}
+
+#[test]
+fn chunk_extensions_and_empty_trailers_are_accepted() {
+    let transcript =
+        b"HTTP/1.1 200 OK\r\ntransfer-encoding: Chunked\r\n\r\n2;ext=\"a b\"\r\n[]\r\n0 ; last\r\n\r\nextra";
+    let policy = HttpPolicy::new(Policy::allow(&[vec!["x"]]));
+    let ranges = try_find_response_ranges(transcript, &policy, &Options::default()).unwrap();
+    assert_eq!(ranges, [[0, 36], [43, 76]]);
+}
+
+#[test]
+fn reports_malformed_chunks() {
+    let head = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n";
+    let with_body = |body: &str| format!("{head}\r\n{body}").into_bytes();
+    assert_eq!(
+        error(&with_body("x\r\n{}\r\n0\r\n\r\n")),
+        (47, HttpErrorKind::InvalidChunk)
+    );
+    assert_eq!(
+        error(&with_body("2 x\r\n{}\r\n0\r\n\r\n")),
+        (47, HttpErrorKind::InvalidChunk)
+    );
+    assert_eq!(
+        error(&with_body("1\r\n{}\r\n0\r\n\r\n")),
+        (51, HttpErrorKind::InvalidChunk)
+    );
+    assert_eq!(
+        error(&with_body("2\r\n{}\r\n0\r\n")),
+        (57, HttpErrorKind::IncompleteBody)
+    );
+    assert_eq!(
+        error(&with_body("5\r\n{}")),
+        (52, HttpErrorKind::IncompleteBody)
+    );
+    assert_eq!(
+        error(&with_body("2\r\n{}\r\n0\r\nbad\r\n\r\n")),
+        (57, HttpErrorKind::InvalidHeader)
+    );
+    assert_eq!(
+        error(&with_body("1\r\n[\r\n1\r\n}\r\n0\r\n\r\n")),
+        (56, body_error(56, 7, 1, ParseErrorKind::UnbalancedBrackets))
+    );
+    assert_eq!(
+        error(format!("{head}Content-Length: 2\r\n\r\n2\r\n{{}}\r\n0\r\n\r\n").as_bytes()),
+        (61, HttpErrorKind::InvalidContentLength)
+    );
+    assert_eq!(
+        error(b"HTTP/1.1 200 OK\r\nTransfer-Encoding: gzip, chunked\r\n\r\n"),
+        (36, HttpErrorKind::UnsupportedEncoding)
+    );
+}
+
+#[test]
+fn truncated_chunked_bodies_are_disclosed_up_to_the_cut() {
+    let policy = HttpPolicy::new(Policy::deny(&[vec!["x"]]));
+    let transcript =
+        b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n6\r\n{\"a\":1\r\n8\r\n,\"b\":";
+    let ranges = find_response_ranges(transcript, &policy, &Options::default());
+    assert_eq!(ranges, [[0, 36], [43, transcript.len()]]);
+}
+
+const HEADERS: &[u8] = b"HTTP/1.1 200 OK\r\nDate: today\r\nSET-COOKIE: sid=1\r\nContent-Type: application/json\r\nX-Token: tok\r\n\r\n{}";
+
+#[test]
+fn headers_can_be_denied() {
+    let mut policy =
+        HttpPolicy::new(Policy::deny(&[vec!["x"]])).redact_headers(&["set-cookie", "x-token"]);
+    policy.headers.default = Disclosure::Reveal;
+    let ranges = find_response_ranges(HEADERS, &policy, &Options::default());
+    assert_eq!(ranges, [[0, 42], [47, 90], [93, 99]]);
+}
+
+#[test]
+fn credential_headers_stay_hidden_when_revealed_by_default() {
+    let transcript = b"HTTP/1.1 401 Unauthorized\r\nWWW-Authenticate: Basic realm=x\r\nSet-Cookie: sid=1\r\nDate: today\r\n\r\n{}";
+    let mut policy = HttpPolicy::new(Policy::deny(&[vec!["x"]]));
+    policy.headers.default = Disclosure::Reveal;
+    let ranges = find_response_ranges(transcript, &policy, &Options::default());
+    assert_eq!(ranges, [[0, 45], [58, 72], [77, transcript.len()]]);
+
+    // A rule naming one still applies
+    let policy = policy.reveal_headers(&["set-cookie"]);
+    let ranges = find_response_ranges(transcript, &policy, &Options::default());
+    assert_eq!(ranges, [[0, 45], [58, transcript.len()]]);
+}
+
+#[test]
+fn header_rules_can_depend_on_the_value() {
+    let policy = HttpPolicy::new(Policy::deny(&[vec!["x"]]))
+        .reveal_headers(&["Date"])
+        .header(HeaderRule::reveal("content-type").containing("json"))
+        .header(HeaderRule::reveal("Set-Cookie").containing("sid"))
+        .header(HeaderRule::reveal("X-Token").containing("TOK"));
+    let ranges = find_response_ranges(HEADERS, &policy, &Options::default());
+    assert_eq!(ranges, [[0, 90], [93, 99]]);
+
+    // Redaction wins over a matching reveal rule
+    let policy = policy.header(HeaderRule::redact("set-cookie").containing("="));
+    let ranges = find_response_ranges(HEADERS, &policy, &Options::default());
+    assert_eq!(ranges, [[0, 42], [47, 90], [93, 99]]);
+}
+
+#[test]
+fn header_rules_apply_to_trailers() {
+    let transcript = chunked(&[b"{}"], "X-Token: tok\r\nDate: today\r\n");
+    let policy = HttpPolicy::new(Policy::deny(&[vec!["x"]])).reveal_headers(&["date"]);
+    let ranges = find_response_ranges(&transcript, &policy, &Options::default());
+    let token = transcript.windows(3).position(|w| w == b"tok").unwrap();
+    let chunked = transcript.windows(7).position(|w| w == b"chunked").unwrap();
+    assert_eq!(
+        ranges,
+        [
+            [0, chunked],
+            [chunked + 7, token],
+            [token + 3, transcript.len()]
+        ]
+    );
+}
@@ -0,0 +1,62 @@
+use json_str_redactor::{
+    find_policy_ranges, find_ranges_with, redact_json, KeyMode, Options, Policy, Selector,
+};
+
+const REPORT: &str =
+    r#"{"name":"Alice","bankruptcy_flag":true,"loans":[{"id":1,"late":false}],"score":700}"#;
+
+fn redact(selectors: &[&str], keys: KeyMode) -> String {
+    let selectors: Vec<Selector> = selectors.iter().map(|s| s.parse().unwrap()).collect();
+    let options = Options {
+        keys,
+        ..Default::default()
+    };
+    redact_json(REPORT, find_ranges_with(REPORT, &selectors, &options))
+}
+
+#[test]
+fn reveals_all_keys_by_default() {
+    assert_eq!(
+        redact(&["$.name"], KeyMode::Reveal),
+        r#"{"name":"Alice","bankruptcy_flag":"<REDACTED>","loans":[{"id":"<REDACTED>","late":"<REDACTED>"}],"score":"<REDACTED>"}"#
+    );
+}
+
+#[test]
+fn redacts_keys_of_undisclosed_members() {
+    assert_eq!(
+        redact(&["$.name"], KeyMode::Redact),
+        r#"{"name":"Alice","<REDACTED>":"<REDACTED>","<REDACTED>":[{"<REDACTED>":"<REDACTED>","<REDACTED>":"<REDACTED>"}],"<REDACTED>":"<REDACTED>"}"#
+    );
+    // Keys on the way to a disclosed value are kept
+    assert_eq!(
+        redact(&["$.loans[0].id"], KeyMode::Redact),
+        r#"{"<REDACTED>":"<REDACTED>","<REDACTED>":"<REDACTED>","loans":[{"id":1,"<REDACTED>":"<REDACTED>"}],"<REDACTED>":"<REDACTED>"}"#
+    );
+}
+
+#[test]
+fn hides_undisclosed_entries() {
+    assert_eq!(
+        redact(&["$.name", "$.score"], KeyMode::HideEntries),
+        r#"{"name":"Alice""<REDACTED>""score":700}"#
+    );
+    assert_eq!(
+        redact(&["$.loans[0].late"], KeyMode::HideEntries),
+        r#"{"<REDACTED>""loans":[{"<REDACTED>""late":false}]"<REDACTED>"}"#
+    );
+    assert_eq!(redact(&[], KeyMode::HideEntries), r#"{"<REDACTED>"}"#);
+}
+
+#[test]
+fn deny_policy_hides_denied_keys() {
+    let policy = Policy::deny(&[Selector::parse("$.bankruptcy_flag").unwrap()]);
+    let options = Options {
+        keys: KeyMode::HideEntries,
+        ..Default::default()
+    };
+    assert_eq!(
+        redact_json(REPORT, find_policy_ranges(REPORT, &policy, &options)),
+        r#"{"name":"Alice""<REDACTED>""loans":[{"id":1,"late":false}],"score":700}"#
+    );
+}
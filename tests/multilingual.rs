@@ -0,0 +1,120 @@
+use json_str_redactor::{
+    convert_ranges, find_ranges, find_ranges_with, redact_json, OffsetUnit, Options,
+};
+
+// Each case is a response body, the key sequences to disclose and the expected
+// redacted rendering
+const CORPUS: &[(&str, &[&[&str]], &str)] = &[
+    (
+        r#"{"name":"José Müller","city":"Zürich","age":41}"#,
+        &[&["city"]],
+        r#"{"name":"<REDACTED>","city":"Zürich","age":"<REDACTED>"}"#,
+    ),
+    (
+        r#"{"user":{"nickname":"小明","bio":"喜欢编程 🚀"},"lang":"zh-CN"}"#,
+        &[&["user", "bio"], &["lang"]],
+        r#"{"user":{"nickname":"<REDACTED>","bio":"喜欢编程 🚀"},"lang":"zh-CN"}"#,
+    ),
+    (
+        r#"{"title":"東京タワー","rating":4.5,"tags":["観光","夜景"]}"#,
+        &[&["rating"]],
+        r#"{"title":"<REDACTED>","rating":4.5,"tags":["<REDACTED>","<REDACTED>"]}"#,
+    ),
+    (
+        r#"{"greeting":"مرحبا","reply":"שלום","ok":true}"#,
+        &[&["reply"], &["ok"]],
+        r#"{"greeting":"<REDACTED>","reply":"שלום","ok":true}"#,
+    ),
+    (
+        r#"{"emoji":"👨‍👩‍👧‍👦","count":1,"flags":["🇩🇪","🇯🇵"]}"#,
+        &[&["flags"]],
+        r#"{"emoji":"<REDACTED>","count":"<REDACTED>","flags":["🇩🇪","🇯🇵"]}"#,
+    ),
+    (
+        r#"{"имя":"Иван","фамилия":"Петров","город":"Москва"}"#,
+        &[&["фамилия"]],
+        r#"{"имя":"<REDACTED>","фамилия":"Петров","город":"<REDACTED>"}"#,
+    ),
+    (
+        "{\n  \"खाता\": \"१२३४\",\n  \"बैंक\": \"भारतीय स्टेट बैंक\"\n}",
+        &[&["बैंक"]],
+        "{\n  \"खाता\": \"<REDACTED>\",\n  \"बैंक\": \"भारतीय स्टेट बैंक\"\n}",
+    ),
+];
+
+fn key_sequences(keys: &[&[&'static str]]) -> Vec<Vec<&'static str>> {
+    keys.iter().map(|keys| keys.to_vec()).collect()
+}
+
+#[test]
+fn ranges_are_byte_offsets_on_char_boundaries() {
+    for &(json, keys, _) in CORPUS {
+        let ranges = find_ranges(json, &key_sequences(keys));
+        for &[start, end] in &ranges {
+            assert!(start < end && end <= json.len(), "{json}: {ranges:?}");
+            assert!(
+                json.is_char_boundary(start) && json.is_char_boundary(end),
+                "{json}: {ranges:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn redacts_multilingual_responses() {
+    for &(json, keys, expected) in CORPUS {
+        let ranges = find_ranges(json, &key_sequences(keys));
+        assert_eq!(redact_json(json, ranges), expected);
+    }
+}
+
+#[test]
+fn disclosed_value_bytes_match_transcript() {
+    let json = r#"{"a":"ä","b":"😀","c":"ü"}"#;
+    let ranges = find_ranges(json, &[vec!["b"]]);
+    // `"ä"` and `"ü"` take 4 bytes each and `"😀"` takes 6
+    assert_eq!(ranges, [[0, 5], [9, 25], [29, 30]]);
+    assert_eq!(&json.as_bytes()[9..25], r#","b":"😀","c":"#.as_bytes());
+}
+
+#[test]
+fn ranges_in_utf16_and_char_units() {
+    let json = r#"{"a":"ä","b":"😀","c":"ü"}"#;
+    let keys = [vec!["b"]];
+    let utf16 = Options {
+        unit: OffsetUnit::Utf16,
+        ..Default::default()
+    };
+    let chars = Options {
+        unit: OffsetUnit::Char,
+        ..Default::default()
+    };
+    // `😀` is a surrogate pair in UTF-16 and a single char
+    assert_eq!(
+        find_ranges_with(json, &keys, &utf16),
+        [[0, 5], [8, 22], [25, 26]]
+    );
+    assert_eq!(
+        find_ranges_with(json, &keys, &chars),
+        [[0, 5], [8, 21], [24, 25]]
+    );
+
+    let bytes = find_ranges(json, &keys);
+    let converted = convert_ranges(json, &bytes, OffsetUnit::Utf8, OffsetUnit::Utf16);
+    assert_eq!(converted, find_ranges_with(json, &keys, &utf16));
+    assert_eq!(
+        convert_ranges(json, &converted, OffsetUnit::Utf16, OffsetUnit::Utf8),
+        bytes
+    );
+}
+
+#[test]
+fn converting_never_widens_ranges() {
+    let json = "a😀b";
+    // Both offsets fall between the halves of the surrogate pair
+    assert_eq!(
+        convert_ranges(json, &[[0, 2], [2, 4]], OffsetUnit::Utf16, OffsetUnit::Utf8),
+        [[0, 1], [5, 6]]
+    );
+    assert!(convert_ranges(json, &[[2, 2]], OffsetUnit::Utf16, OffsetUnit::Utf8).is_empty());
+}
@@ -0,0 +1,99 @@
+use json_str_redactor::{
+    find_policy_ranges, redact_json_with, Options, Part, PlaceholderStyle, Policy, Selector,
+};
+
+const CONTACTS: &str =
+    r#"{"phone":"123-456-7890","email":"alice@example.com","pin":482913,"note":"café \"x\""}"#;
+
+fn reveal(selector: &str, part: Part) -> String {
+    let policy =
+        Policy::allow(&[] as &[Selector]).reveal_part(&[Selector::parse(selector).unwrap()], part);
+    let ranges = find_policy_ranges(CONTACTS, &policy, &Options::default());
+    redact_json_with(CONTACTS, &ranges, PlaceholderStyle::Typed { filler: b'*' })
+}
+
+#[test]
+fn prefixes_suffixes_and_char_ranges() {
+    assert_eq!(
+        reveal("$.phone", Part::Suffix(4)),
+        r#"{"phone":"********7890","email":"*****************","pin":0     ,"note":"***********"}"#
+    );
+    assert_eq!(
+        reveal("$.phone", Part::Prefix(3)),
+        r#"{"phone":"123*********","email":"*****************","pin":0     ,"note":"***********"}"#
+    );
+    assert_eq!(
+        reveal("$.phone", Part::Chars(4, 7)),
+        r#"{"phone":"****456*****","email":"*****************","pin":0     ,"note":"***********"}"#
+    );
+    // Longer than the value reveals all of it
+    assert_eq!(
+        reveal("$.phone", Part::Prefix(100)),
+        r#"{"phone":"123-456-7890","email":"*****************","pin":0     ,"note":"***********"}"#
+    );
+}
+
+#[test]
+fn regex_captures() {
+    assert_eq!(
+        reveal("$.email", Part::capture("@(.+)$").unwrap()),
+        r#"{"phone":"************","email":"******example.com","pin":0     ,"note":"***********"}"#
+    );
+    // Without a group the whole match is revealed
+    assert_eq!(
+        reveal("$.email", Part::capture(r"\.\w+$").unwrap()),
+        r#"{"phone":"************","email":"*************.com","pin":0     ,"note":"***********"}"#
+    );
+    // No match reveals only the quotes
+    assert_eq!(
+        reveal("$.email", Part::capture("^bob").unwrap()),
+        r#"{"phone":"************","email":"*****************","pin":0     ,"note":"***********"}"#
+    );
+}
+
+#[test]
+fn parts_count_decoded_chars() {
+    // `é` and `\"` are one char each and are revealed whole
+    assert_eq!(
+        reveal("$.note", Part::Chars(3, 5)),
+        r#"{"phone":"************","email":"*****************","pin":0     ,"note":"***é *****"}"#
+    );
+    assert_eq!(
+        reveal("$.note", Part::Suffix(2)),
+        r#"{"phone":"************","email":"*****************","pin":0     ,"note":"********x\""}"#
+    );
+    assert_eq!(
+        reveal("$.note", Part::capture("(é)").unwrap()),
+        reveal("$.note", Part::Chars(3, 4))
+    );
+}
+
+#[test]
+fn numbers_reveal_part_of_their_text() {
+    let policy = Policy::allow(&[vec!["x"]]).reveal_part(&[vec!["pin"]], Part::Suffix(2));
+    assert_eq!(
+        find_policy_ranges(CONTACTS, &policy, &Options::default()),
+        [[0, 9], [23, 32], [51, 58], [62, 72], [85, 86]]
+    );
+}
+
+#[test]
+fn partial_rules_apply_below_containers_and_yield_to_closer_rules() {
+    let json = r#"{"cards":[{"number":"4111111111111111","cvv":"123"}]}"#;
+    let policy = Policy::allow(&[vec!["x"]])
+        .reveal_part(&[vec!["cards"]], Part::Suffix(4))
+        .redact(&[vec!["cards", "cvv"]]);
+    let ranges = find_policy_ranges(json, &policy, &Options::default());
+    assert_eq!(
+        redact_json_with(json, &ranges, PlaceholderStyle::Typed { filler: b'*' }),
+        r#"{"cards":[{"number":"************1111","cvv":"***"}]}"#
+    );
+
+    // A partial reveal is more restrictive than a full one on the same value
+    let policy = Policy::allow(&[vec!["cards"]]).reveal_part(&[vec!["cards"]], Part::Suffix(1));
+    let ranges = find_policy_ranges(json, &policy, &Options::default());
+    assert_eq!(
+        redact_json_with(json, &ranges, PlaceholderStyle::Typed { filler: b'*' }),
+        r#"{"cards":[{"number":"***************1","cvv":"**3"}]}"#
+    );
+}
@@ -0,0 +1,104 @@
+use json_str_redactor::{
+    find_policy_ranges, find_ranges, redact_json, redact_json_filled, redact_json_with, Disclosure,
+    KeyMode, Options, PlaceholderStyle, Policy, Selector,
+};
+
+const PROFILE: &str = r#"{"user":{"name":"Alice","ssn":"123-45-6789","email":"a@b.c"},"token":"abc","items":[{"id":1,"secret":"x"},{"id":2,"secret":"y"}]}"#;
+
+fn selectors(selectors: &[&str]) -> Vec<Selector> {
+    selectors.iter().map(|s| s.parse().unwrap()).collect()
+}
+
+fn redact(policy: &Policy) -> String {
+    redact_json(
+        PROFILE,
+        find_policy_ranges(PROFILE, policy, &Options::default()),
+    )
+}
+
+#[test]
+fn allow_policy_matches_find_ranges() {
+    let keys = selectors(&["$.user.name", "$.items[*].id"]);
+    assert_eq!(
+        find_policy_ranges(PROFILE, &Policy::allow(&keys), &Options::default()),
+        find_ranges(PROFILE, &keys)
+    );
+}
+
+#[test]
+fn deny_policy_reveals_everything_else() {
+    assert_eq!(
+        redact(&Policy::deny(&selectors(&[
+            "$.user.ssn",
+            "$.token",
+            "$..secret"
+        ]))),
+        r#"{"user":{"name":"Alice","ssn":"<REDACTED>","email":"a@b.c"},"token":"<REDACTED>","items":[{"id":1,"secret":"<REDACTED>"},{"id":2,"secret":"<REDACTED>"}]}"#
+    );
+    assert_eq!(redact(&Policy::deny(&Vec::<Selector>::new())), PROFILE);
+}
+
+#[test]
+fn closest_rule_takes_precedence() {
+    // Reveal the user except for the ssn
+    assert_eq!(
+        redact(&Policy::allow(&selectors(&["$.user"])).redact(&selectors(&["$.user.ssn"]))),
+        r#"{"user":{"name":"Alice","ssn":"<REDACTED>","email":"a@b.c"},"token":"<REDACTED>","items":[{"id":"<REDACTED>","secret":"<REDACTED>"},{"id":"<REDACTED>","secret":"<REDACTED>"}]}"#
+    );
+    // Hide the items except for their ids, whatever the rule order
+    let policy = Policy::deny(&selectors(&["$.items"])).reveal(&selectors(&["$.items[*].id"]));
+    assert_eq!(
+        redact(&policy),
+        r#"{"user":{"name":"Alice","ssn":"123-45-6789","email":"a@b.c"},"token":"abc","items":[{"id":1,"secret":"<REDACTED>"},{"id":2,"secret":"<REDACTED>"}]}"#
+    );
+    let reordered = Policy {
+        default: Disclosure::Reveal,
+        rules: policy.rules.iter().rev().cloned().collect(),
+    };
+    assert_eq!(redact(&reordered), redact(&policy));
+}
+
+#[test]
+fn redaction_wins_ties() {
+    let policy =
+        Policy::allow(&selectors(&["$.user.email", "$.token"])).redact(&selectors(&["$..email"]));
+    assert_eq!(
+        redact(&policy),
+        r#"{"user":{"name":"<REDACTED>","ssn":"<REDACTED>","email":"<REDACTED>"},"token":"abc","items":[{"id":"<REDACTED>","secret":"<REDACTED>"},{"id":"<REDACTED>","secret":"<REDACTED>"}]}"#
+    );
+}
+
+#[test]
+fn redacting_content_keeps_the_quotes_of_strings() {
+    let policy = Policy::deny(&selectors(&["$.token"]))
+        .redact_content(&selectors(&["$.user.ssn", "$.items[*].id"]));
+    let ranges = find_policy_ranges(PROFILE, &policy, &Options::default());
+    assert_eq!(
+        redact_json_filled(PROFILE, &ranges, b'*'),
+        r#"{"user":{"name":"Alice","ssn":"***********","email":"a@b.c"},"token":*****,"items":[{"id":*,"secret":"x"},{"id":*,"secret":"y"}]}"#
+    );
+
+    // A closer rule overrides it either way
+    let policy = Policy::allow(&selectors(&["$.user.name"]))
+        .redact_content(&selectors(&["$.user"]))
+        .reveal(&selectors(&["$.user.email"]));
+    let ranges = find_policy_ranges(PROFILE, &policy, &Options::default());
+    assert_eq!(
+        redact_json_filled(PROFILE, &ranges, b'*'),
+        r#"{"user":{"name":"Alice","ssn":"***********","email":"a@b.c"},"token":*****,"items":[{"id":*,"secret":***},{"id":*,"secret":***}]}"#
+    );
+}
+
+#[test]
+fn redacted_content_counts_as_disclosed_for_keys() {
+    let options = Options {
+        keys: KeyMode::HideEntries,
+        ..Default::default()
+    };
+    let policy = Policy::allow(&[] as &[Selector]).redact_content(&selectors(&["$..secret"]));
+    let ranges = find_policy_ranges(PROFILE, &policy, &options);
+    assert_eq!(
+        redact_json_with(PROFILE, &ranges, PlaceholderStyle::Marker),
+        r#"{"items":[{"secret":"<REDACTED>"},{"secret":"<REDACTED>"}]}"#
+    );
+}
@@ -0,0 +1,147 @@
+use json_str_redactor::{
+    find_ranges, find_ranges_with, redact_json_filled, redact_json_with, KeyMode, Options,
+    PlaceholderStyle,
+};
+use serde_json::{json, Value};
+
+#[test]
+fn filled_output_is_aligned_with_the_input() {
+    let json = r#"{"name":"Alice","age":30,"tags":["a","b"]}"#;
+    let redacted = redact_json_filled(json, &find_ranges(json, &[vec!["name"]]), b'*');
+    assert_eq!(redacted, r#"{"name":"Alice","age":**,"tags":[***,***]}"#);
+    assert_eq!(redacted.len(), json.len());
+}
+
+#[test]
+fn multi_byte_characters_are_filled_byte_by_byte() {
+    let json = r#"{"a":"é😀","b":"ü"}"#;
+    let redacted = redact_json_filled(json, &find_ranges(json, &[vec!["b"]]), b'X');
+    assert_eq!(redacted, r#"{"a":XXXXXXXX,"b":"ü"}"#);
+    assert_eq!(redacted.len(), json.len());
+}
+
+#[test]
+fn ranges_inside_characters_are_shrunk() {
+    let json = "aéb";
+    // Both ranges end or start in the middle of the two-byte `é`
+    assert_eq!(redact_json_filled(json, &[[0, 2], [2, 4]], b'X'), "aXXb");
+    assert_eq!(redact_json_filled(json, &[[1, 3]], b'X'), "XéX");
+}
+
+fn parses(json: &str) -> Value {
+    serde_json::from_str(json).unwrap_or_else(|error| panic!("{json:?}: {error}"))
+}
+
+#[test]
+fn placeholders_keep_values_and_types() {
+    let json =
+        r#"{"name":"Alice","age":30,"scores":[1,-2.5e3,3],"ok":true,"no":false,"none":null}"#;
+    let ranges = find_ranges(json, &[vec!["name"]]);
+
+    let typed = redact_json_with(json, &ranges, PlaceholderStyle::Typed { filler: b'X' });
+    assert_eq!(
+        typed,
+        r#"{"name":"Alice","age":0 ,"scores":[0,0     ,0],"ok":null,"no":null ,"none":null}"#
+    );
+    assert_eq!(typed.len(), json.len());
+    assert_eq!(
+        parses(&typed),
+        json!({"name": "Alice", "age": 0, "scores": [0, 0, 0], "ok": null, "no": null, "none": null})
+    );
+
+    let marker = redact_json_with(json, &ranges, PlaceholderStyle::Marker);
+    assert_eq!(
+        parses(&marker)["scores"],
+        json!(["<REDACTED>", "<REDACTED>", "<REDACTED>"])
+    );
+    assert_eq!(parses(&marker)["name"], "Alice");
+}
+
+#[test]
+fn strings_keep_their_length() {
+    let json = r#"{"a":"é\"x","b":"ü"}"#;
+    let typed = redact_json_with(
+        json,
+        &find_ranges(json, &[vec!["b"]]),
+        PlaceholderStyle::Typed { filler: b'*' },
+    );
+    assert_eq!(typed, r#"{"a":"*****","b":"ü"}"#);
+    assert_eq!(typed.len(), json.len());
+    assert_eq!(parses(&typed), json!({"a": "*****", "b": "ü"}));
+}
+
+#[test]
+fn partially_kept_strings_stay_valid() {
+    let json = r#"{"a":"ab\ncd"}"#;
+    // Keep up to and including the backslash, hide the rest of the string
+    for style in [
+        PlaceholderStyle::Marker,
+        PlaceholderStyle::Typed { filler: b'X' },
+    ] {
+        let redacted = redact_json_with(json, &[[0, 9], [13, 14]], style);
+        parses(&redacted);
+    }
+    assert_eq!(
+        redact_json_with(json, &[[0, 9], [13, 14]], PlaceholderStyle::Marker),
+        r#"{"a":"ab\\<REDACTED>"}"#
+    );
+}
+
+#[test]
+fn hidden_entries_and_keys_stay_valid() {
+    let json = r#"{ "a" : 1 , "b" : [ 2 , 3 ] , "c" : { "d" : 4 } }"#;
+    for keys in [KeyMode::Reveal, KeyMode::Redact, KeyMode::HideEntries] {
+        let options = Options {
+            keys,
+            ..Default::default()
+        };
+        for selector in [["a"], ["b"], ["c"], ["d"], ["x"]] {
+            let ranges = find_ranges_with(json, &[selector.to_vec()], &options);
+            for style in [
+                PlaceholderStyle::Marker,
+                PlaceholderStyle::Typed { filler: b'X' },
+            ] {
+                let redacted = redact_json_with(json, &ranges, style);
+                let value = parses(&redacted);
+                if selector == ["b"] {
+                    assert_eq!(value["b"], json!([2, 3]), "{redacted}");
+                }
+            }
+        }
+    }
+
+    let options = Options {
+        keys: KeyMode::HideEntries,
+        ..Default::default()
+    };
+    let ranges = find_ranges_with(json, &[vec!["c", "d"]], &options);
+    assert_eq!(
+        parses(&redact_json_with(json, &ranges, PlaceholderStyle::Marker)),
+        json!({"c": {"d": 4}})
+    );
+    let ranges = find_ranges_with(json, &[vec!["x"]], &options);
+    assert_eq!(
+        redact_json_with(json, &ranges, PlaceholderStyle::Marker),
+        "{  }"
+    );
+}
+
+#[test]
+fn top_level_values_and_nothing_kept() {
+    for json in [r#"[1,"a",true]"#, "42", r#""secret""#, r#"{"a":{"b":[]}}"#] {
+        for style in [
+            PlaceholderStyle::Marker,
+            PlaceholderStyle::Typed { filler: b'X' },
+        ] {
+            parses(&redact_json_with(json, &[], style));
+        }
+    }
+    assert_eq!(
+        redact_json_with(
+            r#"[1,"a",true]"#,
+            &[],
+            PlaceholderStyle::Typed { filler: b'X' }
+        ),
+        r#"[0,"X",null]"#
+    );
+}
@@ -0,0 +1,100 @@
+use json_str_redactor::{
+    find_policy_ranges, find_report, Match, OffsetUnit, Options, Policy, Segment, Selector,
+    ValueKind,
+};
+
+const JSON: &str = r#"{"user":{"name":"Zoë","tags":["a",1,true,null,{}]},"ok":false}"#;
+
+fn selectors(selectors: &[&str]) -> Vec<Selector> {
+    selectors.iter().map(|s| s.parse().unwrap()).collect()
+}
+
+#[test]
+fn matches_are_listed_per_rule() {
+    let policy = Policy::allow(&selectors(&["$.user.tags[*]", "$.missing", "$..name"]))
+        .redact(&selectors(&["$.ok"]));
+    let report = find_report(JSON, &policy, &Options::default()).unwrap();
+
+    let tag = |index: i64| {
+        vec![
+            Segment::from("user"),
+            Segment::from("tags"),
+            Segment::Index(index),
+        ]
+    };
+    assert_eq!(
+        report.matches[0],
+        [
+            Match {
+                span: [31, 34],
+                kind: ValueKind::String,
+                path: tag(0)
+            },
+            Match {
+                span: [35, 36],
+                kind: ValueKind::Number,
+                path: tag(1)
+            },
+            Match {
+                span: [37, 41],
+                kind: ValueKind::Bool,
+                path: tag(2)
+            },
+            Match {
+                span: [42, 46],
+                kind: ValueKind::Null,
+                path: tag(3)
+            },
+            Match {
+                span: [47, 49],
+                kind: ValueKind::Object,
+                path: tag(4)
+            },
+        ]
+    );
+    // A path that matched nothing
+    assert!(report.matches[1].is_empty());
+    assert_eq!(
+        report.matches[2],
+        [Match {
+            span: [16, 22],
+            kind: ValueKind::String,
+            path: vec![Segment::from("user"), Segment::from("name")],
+        }]
+    );
+    assert_eq!(report.matches[3][0].kind, ValueKind::Bool);
+    assert_eq!(report.matches[3][0].path, [Segment::from("ok")]);
+}
+
+#[test]
+fn redacted_ranges_complement_the_kept_ones() {
+    let policy = Policy::allow(&selectors(&["$.user.name"]));
+    let report = find_report(JSON, &policy, &Options::default()).unwrap();
+    assert_eq!(
+        report.ranges,
+        find_policy_ranges(JSON, &policy, &Options::default())
+    );
+
+    let mut covered: Vec<[usize; 2]> = [report.ranges.clone(), report.redacted.clone()].concat();
+    covered.sort();
+    let mut cursor = 0;
+    for [start, end] in covered {
+        assert_eq!(start, cursor);
+        cursor = end;
+    }
+    assert_eq!(cursor, JSON.len());
+    assert_eq!(report.redacted[0], [31, 34]);
+}
+
+#[test]
+fn offsets_follow_the_unit() {
+    let options = Options {
+        unit: OffsetUnit::Char,
+        ..Default::default()
+    };
+    let report = find_report(JSON, &Policy::allow(&selectors(&["$.ok"])), &options).unwrap();
+    // `ë` takes two bytes but one char
+    assert_eq!(report.matches[0][0].span, [56, 61]);
+    assert_eq!(report.redacted[0], [16, 21]);
+    assert_eq!(report.ranges.last().unwrap()[1], JSON.chars().count());
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use common::body_error;
+use json_str_redactor::{
+    find_request_ranges, try_find_request_ranges, Disclosure, HttpErrorKind, Options,
+    ParseErrorKind, Policy, RequestPolicy,
+};
+
+fn error(transcript: &[u8]) -> (usize, HttpErrorKind) {
+    let policy = RequestPolicy::new(Policy::allow(&[vec!["id"]]));
+    common::error(try_find_request_ranges(
+        transcript,
+        &policy,
+        &Options::default(),
+    ))
+}
+
+#[test]
+fn credentials_and_json_bodies_are_redacted() {
+    let transcript = b"POST /api/v1/pay HTTP/1.1\r\nHost: bank.example\r\nAuthorization: Bearer abc\r\nCookie: sid=1\r\nContent-Length: 26\r\n\r\n{\"amount\":5,\"card\":\"4111\"}";
+    let mut policy = RequestPolicy::new(Policy::deny(&[vec!["card"]]))
+        .reveal_path_segments(&[0, 2])
+        .redact_headers(&["authorization", "COOKIE"]);
+    policy.headers.default = Disclosure::Reveal;
+    let ranges = find_request_ranges(transcript, &policy, &Options::default());
+    assert_eq!(ranges, [[0, 10], [12, 62], [72, 82], [87, 130], [136, 137]]);
+    assert_eq!(
+        try_find_request_ranges(transcript, &policy, &Options::default()),
+        Ok(ranges)
+    );
+
+    // Header values are redacted by default
+    let policy = RequestPolicy::new(Policy::deny(&[vec!["card"]])).reveal_headers(&["host"]);
+    let ranges = find_request_ranges(transcript, &policy, &Options::default());
+    assert_eq!(
+        ranges,
+        [
+            [0, 6],
+            [9, 10],
+            [12, 13],
+            [16, 62],
+            [72, 82],
+            [87, 105],
+            [107, 130],
+            [136, 137]
+        ]
+    );
+}
+
+#[test]
+fn path_segments_and_query_values_are_revealed_selectively() {
+    let transcript = b"GET /a//b/?q=1&flag&x=&q=2#top HTTP/1.1\r\n\r\nGET / HTTP/1.1\r\n\r\n";
+    let policy = RequestPolicy::new(Policy::allow(&[vec!["id"]]))
+        .reveal_path_segments(&[2])
+        .reveal_query(&["q"]);
+    // Bytes after the request, which has no body, are not disclosed
+    let ranges = find_request_ranges(transcript, &policy, &Options::default());
+    assert_eq!(ranges, [[0, 5], [6, 15], [19, 27], [30, 43]]);
+    assert_eq!(
+        try_find_request_ranges(transcript, &policy, &Options::default()),
+        Ok(ranges)
+    );
+
+    let transcript = b"OPTIONS * HTTP/1.1\r\n\r\n";
+    let ranges = find_request_ranges(transcript, &policy, &Options::default());
+    assert_eq!(ranges, [[0, transcript.len()]]);
+}
+
+#[test]
+fn chunked_request_bodies_are_decoded() {
+    let transcript = b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n4\r\n{\"id\r\n6\r\n\":7,\"x\r\n6\r\n\":\"a\"}\r\n0\r\n\r\n";
+    let policy =
+        RequestPolicy::new(Policy::allow(&[vec!["id"]])).reveal_headers(&["transfer-encoding"]);
+    let ranges = try_find_request_ranges(transcript, &policy, &Options::default()).unwrap();
+    let value = transcript.windows(3).position(|w| w == b"\"a\"").unwrap();
+    assert_eq!(ranges, [[0, value], [value + 3, transcript.len()]]);
+}
+
+#[test]
+fn reports_malformed_requests() {
+    assert_eq!(
+        error(b"GET http://example.com/ HTTP/1.1\r\n\r\n"),
+        (0, HttpErrorKind::InvalidStartLine)
+    );
+    assert_eq!(
+        error(b"GET /a b HTTP/1.1\r\n\r\n"),
+        (0, HttpErrorKind::InvalidStartLine)
+    );
+    assert_eq!(
+        error(b"HTTP/1.1 200 OK\r\n\r\n"),
+        (0, HttpErrorKind::InvalidStartLine)
+    );
+    assert_eq!(
+        error(b"POST / HTTP/1.1\r\nContent-Length: 9\r\n\r\n{}"),
+        (40, HttpErrorKind::IncompleteBody)
+    );
+    assert_eq!(
+        error(b"POST / HTTP/1.1\r\nContent-Length: 2\r\n\r\n[}"),
+        (39, body_error(39, 4, 2, ParseErrorKind::UnbalancedBrackets))
+    );
+    // An empty body is fine
+    let policy = RequestPolicy::new(Policy::allow(&[vec!["id"]]));
+    let transcript = b"POST / HTTP/1.1\r\nContent-Length: 0\r\n\r\n";
+    assert_eq!(
+        try_find_request_ranges(transcript, &policy, &Options::default()),
+        Ok(vec![[0, 33], [34, 38]])
+    );
+    assert!(find_request_ranges(b"GET / HTTP/1.1\r\n", &policy, &Options::default()).is_empty());
+}
+
+#[test]
+fn credentials_stay_redacted_when_headers_are_revealed_by_default() {
+    let transcript = b"GET /a HTTP/1.1\r\nAuthorization: Bearer s\r\nproxy-authorization: Basic p\r\nCookie: c=1\r\nAccept: */*\r\n\r\n";
+    let mut policy = RequestPolicy::new(Policy::allow(&[vec!["id"]]));
+    policy.headers.default = Disclosure::Reveal;
+    let ranges = find_request_ranges(transcript, &policy, &Options::default());
+    assert_eq!(ranges, [[0, 5], [6, 32], [40, 63], [70, 80], [83, 100]]);
+
+    // A rule naming one of them applies as usual
+    let policy = policy.reveal_headers(&["authorization"]);
+    let ranges = find_request_ranges(transcript, &policy, &Options::default());
+    assert_eq!(ranges, [[0, 5], [6, 63], [70, 80], [83, 100]]);
+}
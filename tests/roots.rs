@@ -0,0 +1,58 @@
+use json_str_redactor::{find_ranges, redact_json, Segment, Selector};
+
+fn selectors(selectors: &[&str]) -> Vec<Selector> {
+    selectors.iter().map(|s| s.parse().unwrap()).collect()
+}
+
+#[test]
+fn root_arrays() {
+    let json = r#"[{"id":1,"name":"a"},{"id":2,"name":"b"}]"#;
+    // Keys look through the root array like any other
+    assert_eq!(
+        redact_json(json, find_ranges(json, &[vec!["id"]])),
+        r#"[{"id":1,"name":"<REDACTED>"},{"id":2,"name":"<REDACTED>"}]"#
+    );
+    assert_eq!(
+        redact_json(json, find_ranges(json, &selectors(&["$[1].name"]))),
+        r#"[{"id":"<REDACTED>","name":"<REDACTED>"},{"id":"<REDACTED>","name":"b"}]"#
+    );
+    assert_eq!(
+        find_ranges(json, &selectors(&["$[-1]"])),
+        find_ranges(json, &[vec![Segment::Index(-1)]])
+    );
+    assert_eq!(
+        redact_json(json, find_ranges(json, &selectors(&["$[*]"]))),
+        json
+    );
+}
+
+#[test]
+fn nested_root_arrays_and_scalars() {
+    let json = " [[1, 2], [3, \"x\"], true] ";
+    assert_eq!(
+        find_ranges(json, &selectors(&["$[1][1]", "$[0][0]"])),
+        [[0, 6], [7, 11], [12, 20], [24, 26]]
+    );
+    assert_eq!(
+        redact_json(json, find_ranges(json, &selectors(&["$[2]"]))),
+        r#" [["<REDACTED>", "<REDACTED>"], ["<REDACTED>", "<REDACTED>"], true] "#
+    );
+}
+
+#[test]
+fn scalar_documents() {
+    for json in [r#""ok""#, "42", "true", "null", " \"a b\"\n"] {
+        let everything = selectors(&["$"]);
+        assert_eq!(find_ranges(json, &everything), [[0, json.len()]]);
+        let nothing = selectors(&["$.x"]);
+        let kept: usize = find_ranges(json, &nothing)
+            .iter()
+            .map(|[start, end]| end - start)
+            .sum();
+        assert_eq!(kept, json.len() - json.trim().len());
+    }
+    assert_eq!(
+        redact_json("42", find_ranges("42", &[vec!["x"]])),
+        "\"<REDACTED>\""
+    );
+}
@@ -0,0 +1,92 @@
+use json_str_redactor::{Scanner, Token, TokenKind};
+
+fn tokens(json: &str) -> Vec<(TokenKind, usize, usize)> {
+    let tokens: Vec<_> = Scanner::new(json)
+        .map(|Token { kind, start, end }| (kind, start, end))
+        .collect();
+    // Tokens cover every byte of the input, in order
+    let mut cursor = 0;
+    for &(_, start, end) in &tokens {
+        assert_eq!(start, cursor, "{json:?}");
+        assert!(start < end, "{json:?}");
+        cursor = end;
+    }
+    assert_eq!(cursor, json.len(), "{json:?}");
+    tokens
+}
+
+#[test]
+fn escapes_before_the_closing_quote() {
+    use TokenKind::*;
+    assert_eq!(tokens(r#""a\"" "#), [(String, 0, 5), (Whitespace, 5, 6)]);
+    assert_eq!(tokens(r#""\\"x"#), [(String, 0, 4), (Literal, 4, 5)]);
+    assert_eq!(
+        tokens(r#"["\\\"",1]"#),
+        [
+            (BeginArray, 0, 1),
+            (String, 1, 7),
+            (Comma, 7, 8),
+            (Number, 8, 9),
+            (EndArray, 9, 10)
+        ]
+    );
+}
+
+#[test]
+fn unterminated_strings_run_to_the_end() {
+    use TokenKind::*;
+    assert_eq!(tokens(r#"["ab"#), [(BeginArray, 0, 1), (String, 1, 4)]);
+    assert_eq!(tokens(r#""a\"]"#), [(String, 0, 5)]);
+    assert_eq!(tokens("\"é"), [(String, 0, 3)]);
+    // A backslash as the last byte does not step past the end
+    assert_eq!(tokens(r#""a\"#), [(String, 0, 3)]);
+    assert_eq!(tokens(r#"{"\"#), [(BeginObject, 0, 1), (String, 1, 3)]);
+}
+
+#[test]
+fn runs_of_whitespace_are_one_token() {
+    use TokenKind::*;
+    assert_eq!(
+        tokens(" \t\r\n 1\n\t"),
+        [(Whitespace, 0, 5), (Number, 5, 6), (Whitespace, 6, 8)]
+    );
+    assert_eq!(
+        tokens("[ \n]\r\n"),
+        [
+            (BeginArray, 0, 1),
+            (Whitespace, 1, 3),
+            (EndArray, 3, 4),
+            (Whitespace, 4, 6)
+        ]
+    );
+}
+
+#[test]
+fn bare_words_end_at_quotes_and_punctuation() {
+    use TokenKind::*;
+    assert_eq!(
+        tokens(r#"[tru"x",nul]-1:"#),
+        [
+            (BeginArray, 0, 1),
+            (Literal, 1, 4),
+            (String, 4, 7),
+            (Comma, 7, 8),
+            (Literal, 8, 11),
+            (EndArray, 11, 12),
+            (Number, 12, 14),
+            (Colon, 14, 15)
+        ]
+    );
+    assert_eq!(
+        tokens("1e5}-x{é\tnull"),
+        [
+            (Number, 0, 3),
+            (EndObject, 3, 4),
+            (Number, 4, 6),
+            (BeginObject, 6, 7),
+            (Literal, 7, 9),
+            (Whitespace, 9, 10),
+            (Literal, 10, 14)
+        ]
+    );
+}
@@ -0,0 +1,99 @@
+use json_str_redactor::{find_ranges, redact_json, Segment};
+
+const CONTACTS: &str = r#"{"contacts":[{"type":"email","value":"a@b.c"},{"type":"phone","value":"123"},{"type":"fax","value":"456"}]}"#;
+
+fn redact(json: &str, keys: &[Vec<Segment>]) -> String {
+    redact_json(json, find_ranges(json, keys))
+}
+
+fn path(segments: &[Segment]) -> Vec<Segment> {
+    segments.to_vec()
+}
+
+fn key(key: &str) -> Segment {
+    Segment::from(key)
+}
+
+#[test]
+fn index_selects_a_single_element() {
+    assert_eq!(
+        redact(
+            CONTACTS,
+            &[path(&[key("contacts"), Segment::Index(0), key("type")])]
+        ),
+        r#"{"contacts":[{"type":"email","value":"<REDACTED>"},{"type":"<REDACTED>","value":"<REDACTED>"},{"type":"<REDACTED>","value":"<REDACTED>"}]}"#
+    );
+}
+
+#[test]
+fn negative_index_counts_from_the_end() {
+    assert_eq!(
+        redact(CONTACTS, &[path(&[key("contacts"), Segment::Index(-1)])]),
+        r#"{"contacts":[{"type":"<REDACTED>","value":"<REDACTED>"},{"type":"<REDACTED>","value":"<REDACTED>"},{"type":"fax","value":"456"}]}"#
+    );
+    assert_eq!(
+        redact(CONTACTS, &[path(&[key("contacts"), Segment::Index(-4)])]),
+        redact(CONTACTS, &[])
+    );
+}
+
+#[test]
+fn slice_selects_an_index_range() {
+    assert_eq!(
+        redact(
+            CONTACTS,
+            &[path(&[
+                key("contacts"),
+                Segment::Slice(Some(1), None),
+                key("value")
+            ])]
+        ),
+        r#"{"contacts":[{"type":"<REDACTED>","value":"<REDACTED>"},{"type":"<REDACTED>","value":"123"},{"type":"<REDACTED>","value":"456"}]}"#
+    );
+    assert_eq!(
+        redact(
+            CONTACTS,
+            &[path(&[
+                key("contacts"),
+                Segment::Slice(None, Some(-2)),
+                key("type")
+            ])]
+        ),
+        redact(
+            CONTACTS,
+            &[path(&[key("contacts"), Segment::Index(0), key("type")])]
+        )
+    );
+}
+
+#[test]
+fn wildcard_matches_elements_and_members() {
+    let transparent = redact(CONTACTS, &[path(&[key("contacts"), key("type")])]);
+    assert_eq!(
+        redact(
+            CONTACTS,
+            &[path(&[key("contacts"), Segment::Wildcard, key("type")])]
+        ),
+        transparent
+    );
+    assert_eq!(
+        redact(
+            CONTACTS,
+            &[path(&[
+                key("contacts"),
+                Segment::Index(1),
+                Segment::Wildcard
+            ])]
+        ),
+        r#"{"contacts":[{"type":"<REDACTED>","value":"<REDACTED>"},{"type":"phone","value":"123"},{"type":"<REDACTED>","value":"<REDACTED>"}]}"#
+    );
+}
+
+#[test]
+fn index_does_not_match_object_members() {
+    let json = r#"{"a":{"0":"x"}}"#;
+    assert_eq!(
+        redact(json, &[path(&[key("a"), Segment::Index(0)])]),
+        r#"{"a":{"0":"<REDACTED>"}}"#
+    );
+}
@@ -0,0 +1,91 @@
+use json_str_redactor::{find_ranges, Segment, Selector, SelectorError, SelectorErrorKind};
+
+fn segments(selector: &str) -> Vec<Segment> {
+    Selector::parse(selector).unwrap().into()
+}
+
+fn error(selector: &str) -> (usize, SelectorErrorKind) {
+    let SelectorError { position, kind } = Selector::parse(selector).unwrap_err();
+    (position, kind)
+}
+
+#[test]
+fn parses_members_indices_slices_and_wildcards() {
+    assert_eq!(segments("$"), []);
+    assert_eq!(segments("$.name"), [Segment::from("name")]);
+    assert_eq!(
+        segments("$.contacts[*].type"),
+        [
+            Segment::from("contacts"),
+            Segment::Wildcard,
+            Segment::from("type")
+        ]
+    );
+    assert_eq!(
+        segments("$['first name'][\"x\\\"y\"].*"),
+        [
+            Segment::from("first name"),
+            Segment::from("x\"y"),
+            Segment::Wildcard
+        ]
+    );
+    assert_eq!(
+        segments("$.items[-1][ 1 : 3 ][:2][-2:][:]"),
+        [
+            Segment::from("items"),
+            Segment::Index(-1),
+            Segment::Slice(Some(1), Some(3)),
+            Segment::Slice(None, Some(2)),
+            Segment::Slice(Some(-2), None),
+            Segment::Slice(None, None),
+        ]
+    );
+    assert_eq!(
+        segments("$.café['\\u00e9\\ud83d\\ude00']"),
+        [Segment::from("café"), Segment::from("é😀")]
+    );
+}
+
+#[test]
+fn reports_error_positions() {
+    assert_eq!(error("name"), (0, SelectorErrorKind::MissingRoot));
+    assert_eq!(error("$."), (2, SelectorErrorKind::EmptyName));
+    assert_eq!(error("$.a...b"), (5, SelectorErrorKind::EmptyName));
+    assert_eq!(error("$.."), (3, SelectorErrorKind::EmptyName));
+    assert_eq!(error("$.a[0"), (5, SelectorErrorKind::UnexpectedEnd));
+    assert_eq!(error("$.a[x]"), (4, SelectorErrorKind::InvalidIndex));
+    assert_eq!(error("$.a[-]"), (4, SelectorErrorKind::InvalidIndex));
+    assert_eq!(error("$.a['b]"), (7, SelectorErrorKind::UnterminatedString));
+    assert_eq!(error("$['\\q']"), (3, SelectorErrorKind::InvalidEscape));
+    assert_eq!(error("$.a b"), (4, SelectorErrorKind::UnexpectedChar('b')));
+    assert_eq!(error("$.a[0}"), (5, SelectorErrorKind::UnexpectedChar('}')));
+    assert_eq!(
+        Selector::parse("$.a[0}").unwrap_err().to_string(),
+        "unexpected character '}' at offset 5"
+    );
+}
+
+#[test]
+fn selectors_and_key_sequences_give_the_same_ranges() {
+    let json = r#"{"name":"Alice","age":30,"contacts":[{"type":"email","value":"alice@email.com"},{"type":"phone","value":"123-456-7890"}],"isActive":true}"#;
+    let selectors: Vec<Selector> = ["$.contacts[*].type", "$.name"]
+        .iter()
+        .map(|selector| selector.parse().unwrap())
+        .collect();
+    assert_eq!(
+        find_ranges(json, &selectors),
+        find_ranges(json, &[vec!["contacts", "type"], vec!["name"]])
+    );
+    assert_eq!(
+        find_ranges(json, &[Selector::parse("$.contacts[1].value").unwrap()]),
+        [
+            [0, 8],
+            [15, 22],
+            [24, 45],
+            [52, 61],
+            [78, 88],
+            [95, 132],
+            [136, 137]
+        ]
+    );
+}
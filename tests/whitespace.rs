@@ -0,0 +1,77 @@
+use json_str_redactor::{
+    find_policy_ranges, find_ranges, find_ranges_with, redact_json_filled, KeyMode, Options,
+    Policy, Selector, WhitespaceMode,
+};
+
+const PRETTY: &str = "{\n\t\"name\": \"Al ice\",\r\n  \"tags\" :[ 1 ,\n    2 ],\n\t\"age\":30\n}\n";
+
+fn render(keys: &[Vec<&str>], options: &Options) -> String {
+    redact_json_filled(PRETTY, &find_ranges_with(PRETTY, keys, options), b'*')
+}
+
+fn whitespace(whitespace: WhitespaceMode) -> Options {
+    Options {
+        whitespace,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn any_indentation_is_revealed_by_default() {
+    assert_eq!(
+        render(&[vec!["name"]], &Options::default()),
+        "{\n\t\"name\": \"Al ice\",\r\n  \"tags\" :[ * ,\n    * ],\n\t\"age\":**\n}\n"
+    );
+    assert_eq!(
+        find_ranges(PRETTY, &[vec!["name"]]),
+        find_ranges_with(PRETTY, &[vec!["name"]], &whitespace(WhitespaceMode::Reveal))
+    );
+}
+
+#[test]
+fn whitespace_can_be_redacted_everywhere() {
+    let options = whitespace(WhitespaceMode::Redact);
+    assert_eq!(
+        render(&[vec!["name"], vec!["tags"]], &options),
+        "{**\"name\":*\"Al ice\",****\"tags\"*:[*1*,*****2*],**\"age\":***}*"
+    );
+    // Whitespace inside strings is part of the value
+    assert_eq!(
+        render(&[vec![] as Vec<&str>], &options),
+        "{**\"name\":*\"Al ice\",****\"tags\"*:[*1*,*****2*],**\"age\":30*}*"
+    );
+}
+
+#[test]
+fn whitespace_can_follow_disclosure() {
+    let options = whitespace(WhitespaceMode::Disclosed);
+    assert_eq!(
+        render(&[vec!["tags"]], &options),
+        "{**\"name\":*********,****\"tags\"*:[ 1 ,\n    2 ],**\"age\":***}*"
+    );
+    assert_eq!(
+        render(&[vec![] as Vec<&str>], &options),
+        "{\n\t\"name\": \"Al ice\",\r\n  \"tags\" :[ 1 ,\n    2 ],\n\t\"age\":30\n}*"
+    );
+
+    // A value redacted inside a revealed one loses its whitespace
+    let policy = Policy::deny(&[Selector::parse("$.tags").unwrap()]);
+    let ranges = find_policy_ranges(PRETTY, &policy, &options);
+    assert_eq!(
+        redact_json_filled(PRETTY, &ranges, b'*'),
+        "{\n\t\"name\": \"Al ice\",\r\n  \"tags\" :[***,*******],\n\t\"age\":30\n}*"
+    );
+}
+
+#[test]
+fn hidden_entries_take_their_whitespace_along() {
+    let options = Options {
+        keys: KeyMode::HideEntries,
+        whitespace: WhitespaceMode::Reveal,
+        ..Default::default()
+    };
+    assert_eq!(
+        render(&[vec!["age"]], &options),
+        "{\n\t*********************************************\"age\":30\n}\n"
+    );
+}